@@ -1,14 +1,120 @@
-use crate::models::{DateDetail, DateRange};
-use chrono::NaiveDate;
+use crate::csv::CsvEvent;
+use crate::ics::IcsEvent;
+use crate::models::{DateDetail, DateRange, Milestone};
+use crate::rendering::{parse_hex_color, ColorPalette, ColorValue};
+use anstyle::{Effects, RgbColor};
+use chrono::{Datelike, NaiveDate, NaiveTime, Weekday};
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::path::Path;
 
-#[derive(Debug, Deserialize)]
+/// The file format a config is written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    /// Needs the `yaml` feature
+    Yaml,
+}
+
+impl ConfigFormat {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "toml" => Ok(Self::Toml),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            _ => Err(format!(
+                "Invalid config format: '{}'. Use one of: toml, yaml",
+                name
+            )),
+        }
+    }
+
+    /// The format a config path's extension names, TOML unless it is
+    /// `.yaml` or `.yml`
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+                Self::Yaml
+            }
+            _ => Self::Toml,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Clone)]
 pub struct CalendarConfig {
     #[serde(default)]
-    pub dates: HashMap<String, RawDateDetail>,
+    pub settings: Settings,
+    /// Events by date. A date takes one table, or an array of tables for
+    /// several events on the same day.
+    #[serde(default, deserialize_with = "one_or_many_details")]
+    pub dates: HashMap<String, Vec<RawDateDetail>>,
     #[serde(default)]
     pub ranges: Vec<RawDateRange>,
+    #[serde(default, rename = "milestone")]
+    pub milestones: Vec<RawMilestone>,
+    /// Custom color names mapped to palette colors or other aliases
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    /// User-defined colors, taking precedence over builtins of the same name
+    #[serde(default)]
+    pub colors: HashMap<String, RawColor>,
+    /// Labels for the legend, keyed by color name
+    #[serde(default)]
+    pub legend: HashMap<String, String>,
+}
+
+/// A color from the `[colors]` table: `[r, g, b]`, `"#rrggbb"`, or a
+/// `{ normal = ..., dimmed = ... }` table to choose the dimmed shade
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum RawColor {
+    Rgb([u8; 3]),
+    Hex(String),
+    Shades {
+        normal: Box<RawColor>,
+        dimmed: Box<RawColor>,
+    },
+}
+
+impl RawColor {
+    /// A single color; shade tables are only allowed at the top level
+    pub fn to_rgb(&self) -> Result<RgbColor, String> {
+        match self {
+            Self::Rgb([r, g, b]) => Ok(RgbColor(*r, *g, *b)),
+            Self::Hex(hex) => parse_hex_color(hex),
+            Self::Shades { .. } => Err("normal and dimmed must be single colors".to_string()),
+        }
+    }
+
+    /// The color and its dimmed shade, derived unless given
+    pub fn to_color_value(&self) -> Result<ColorValue, String> {
+        match self {
+            Self::Shades { normal, dimmed } => {
+                Ok(ColorValue::new(normal.to_rgb()?, dimmed.to_rgb()?))
+            }
+            _ => self.to_rgb().map(ColorValue::from_rgb),
+        }
+    }
+}
+
+/// Display settings from the `[settings]` table
+#[derive(Debug, Default, Deserialize, Clone)]
+pub struct Settings {
+    /// Title in the header, with `{year}` replaced by the calendar's year
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Second line shown under the title in the header
+    #[serde(default)]
+    pub subtitle: Option<String>,
+    /// Colors for severities 1 through 5, replacing the green to red default
+    #[serde(default)]
+    pub severity_colors: Option<Vec<String>>,
+    /// Background color for today's date
+    #[serde(default)]
+    pub today_color: Option<String>,
+    /// Days of the weekend, e.g. `["fri", "sat"]`
+    #[serde(default)]
+    pub weekend_days: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -17,6 +123,73 @@ pub struct RawDateDetail {
     pub description: String,
     #[serde(default)]
     pub color: Option<String>,
+    #[serde(default)]
+    pub important: bool,
+    /// Listed under the calendar even when the month filter hides it
+    #[serde(default)]
+    pub pinned: bool,
+    /// 1 (low) to 5 (critical); picks the color when none is given
+    #[serde(default)]
+    pub severity: Option<u8>,
+    /// Repeat a full `YYYY-MM-DD` date every year, e.g. for birthdays
+    #[serde(default)]
+    pub recurring: Option<Recurrence>,
+    /// Start time as `HH:MM`, shown between the date and description
+    #[serde(default)]
+    pub time: Option<String>,
+    /// Text effects such as `["bold", "underline"]`
+    #[serde(default)]
+    pub effects: Vec<String>,
+    /// Dim the color on weekends (`true`) or keep it solid (`false`),
+    /// instead of following the weekend display
+    #[serde(default)]
+    pub dim_weekends: Option<bool>,
+}
+
+/// Read each date's events from either a single table or an array of them
+fn one_or_many_details<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HashMap<String, Vec<RawDateDetail>>, D::Error> {
+    use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+    use serde::de::{MapAccess, SeqAccess, Visitor};
+
+    struct OneOrMany(Vec<RawDateDetail>);
+
+    impl<'de> Deserialize<'de> for OneOrMany {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct DetailsVisitor;
+
+            impl<'de> Visitor<'de> for DetailsVisitor {
+                type Value = Vec<RawDateDetail>;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str("an event table or an array of event tables")
+                }
+
+                fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                    RawDateDetail::deserialize(MapAccessDeserializer::new(map)).map(|d| vec![d])
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                    Vec::deserialize(SeqAccessDeserializer::new(seq))
+                }
+            }
+
+            deserializer.deserialize_any(DetailsVisitor).map(OneOrMany)
+        }
+    }
+
+    let dates = HashMap::<String, OneOrMany>::deserialize(deserializer)?;
+    Ok(dates
+        .into_iter()
+        .map(|(date, OneOrMany(details))| (date, details))
+        .collect())
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Recurrence {
+    Yearly,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -26,74 +199,505 @@ pub struct RawDateRange {
     pub color: String,
     #[serde(default)]
     pub description: Option<String>,
+    #[serde(default)]
+    pub important: bool,
+    #[serde(default)]
+    pub pinned: bool,
+    /// Text effects such as `["bold", "underline"]`
+    #[serde(default)]
+    pub effects: Vec<String>,
+    /// Dim the color on weekends (`true`) or keep it solid (`false`),
+    /// instead of following the weekend display
+    #[serde(default)]
+    pub dim_weekends: Option<bool>,
+    /// Whether `end` is the range's last day (`true`, the default) or the
+    /// day after it, as iCalendar and many other tools write ends
+    #[serde(default = "default_end_inclusive")]
+    pub end_inclusive: bool,
+}
+
+fn default_end_inclusive() -> bool {
+    true
+}
+
+impl RawDateRange {
+    /// The range's 1-based position in the config and its description,
+    /// for error messages
+    fn label(&self, idx: usize) -> String {
+        match &self.description {
+            Some(desc) => format!("Range {} ({})", idx + 1, desc),
+            None => format!("Range {}", idx + 1),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct RawMilestone {
+    pub date: String,
+    pub label: String,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Color for ranges imported from an iCalendar file
+pub const IMPORTED_RANGE_COLOR: &str = "blue";
+
+/// Parse a full `YYYY-MM-DD` date, or a yearly `MM-DD` date within `year`
+pub(crate) fn parse_date_for_year(date_str: &str, year: i32) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+        .ok()
+        .or_else(|| {
+            let (month, day) = date_str.split_once('-')?;
+            yearly_date(year, month.parse().ok()?, day.parse().ok()?)
+        })
+}
+
+/// `month`/`day` in `year`, with February 29 falling back to the 28th
+fn yearly_date(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, day).or_else(|| {
+        if (month, day) == (2, 29) {
+            NaiveDate::from_ymd_opt(year, 2, 28)
+        } else {
+            None
+        }
+    })
+}
+
+/// Severity 1 through 5, from green to red
+const DEFAULT_SEVERITY_COLORS: [&str; 5] = ["green", "yellow", "orange", "light_red", "red"];
+
+/// Add `detail` to the date's events, after any of the same or higher
+/// severity, so the most severe colors the day
+fn insert_by_severity(
+    details: &mut HashMap<NaiveDate, Vec<DateDetail>>,
+    date: NaiveDate,
+    detail: DateDetail,
+) {
+    insert_by_severity_after(details, date, detail, 0);
+}
+
+/// Like [`insert_by_severity`], but leaves the date's first `skip` events in
+/// place ahead of it
+fn insert_by_severity_after(
+    details: &mut HashMap<NaiveDate, Vec<DateDetail>>,
+    date: NaiveDate,
+    detail: DateDetail,
+    skip: usize,
+) {
+    let events = details.entry(date).or_default();
+    let skip = skip.min(events.len());
+    let idx = events[skip..]
+        .iter()
+        .position(|existing| existing.severity < detail.severity)
+        .map_or(events.len(), |idx| skip + idx);
+    events.insert(idx, detail);
 }
 
 impl CalendarConfig {
-    pub fn parse_dates(&self) -> HashMap<NaiveDate, DateDetail> {
-        self.dates
-            .iter()
-            .filter_map(|(date_str, detail)| {
-                NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-                    .ok()
-                    .map(|date| {
-                        (
-                            date,
-                            DateDetail {
-                                description: detail.description.clone(),
-                                color: detail.color.clone(),
-                            },
-                        )
-                    })
-            })
+    /// Every event with its date key, in key order and then in the order
+    /// written
+    pub fn date_entries(&self) -> Vec<(&String, &RawDateDetail)> {
+        let mut dates: Vec<&String> = self.dates.keys().collect();
+        dates.sort();
+        dates
+            .into_iter()
+            .flat_map(|date| self.dates[date].iter().map(move |detail| (date, detail)))
             .collect()
     }
 
-    pub fn parse_dates_for_year(&self, year: i32) -> HashMap<NaiveDate, DateDetail> {
-        self.dates
-            .iter()
-            .flat_map(|(date_str, detail)| {
-                if let Ok(date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
-                    return vec![(
-                        date,
-                        DateDetail {
-                            description: detail.description.clone(),
-                            color: detail.color.clone(),
-                        },
-                    )];
+    /// Check severities are within 1 to 5 and the color table covers them
+    pub fn validate_severities(&self) -> Result<(), String> {
+        for (date, detail) in self.date_entries() {
+            if let Some(severity) = detail.severity {
+                if !(1..=5).contains(&severity) {
+                    return Err(format!(
+                        "Severity for {} must be 1-5, got {}",
+                        date, severity
+                    ));
+                }
+            }
+        }
+        if let Some(colors) = &self.settings.severity_colors {
+            if colors.len() != 5 {
+                return Err(format!(
+                    "severity_colors needs 5 colors, got {}",
+                    colors.len()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that no range ends before it starts
+    pub fn validate_ranges(&self) -> Result<(), String> {
+        for (idx, range) in self.ranges.iter().enumerate() {
+            // Recurring MM-DD ranges are compared within a leap year
+            let bounds = match (
+                NaiveDate::parse_from_str(&range.start, "%Y-%m-%d"),
+                NaiveDate::parse_from_str(&range.end, "%Y-%m-%d"),
+            ) {
+                (Ok(start), Ok(end)) => Some((start, end)),
+                (Err(_), Err(_)) => parse_date_for_year(&range.start, 2000)
+                    .zip(parse_date_for_year(&range.end, 2000)),
+                _ => None,
+            };
+            if let Some((start, end)) = bounds {
+                if end < start {
+                    return Err(format!(
+                        "{}: end {} is before start {}",
+                        range.label(idx),
+                        range.end,
+                        range.start
+                    ));
                 }
-                if let Ok(md) =
-                    chrono::NaiveDate::parse_from_str(&format!("{}-{}", year, date_str), "%Y-%m-%d")
-                {
-                    return vec![(
-                        md,
-                        DateDetail {
-                            description: detail.description.clone(),
-                            color: detail.color.clone(),
-                        },
-                    )];
+                if !range.end_inclusive && end == start {
+                    return Err(format!(
+                        "{}: exclusive end {} leaves no days after start {}",
+                        range.label(idx),
+                        range.end,
+                        range.start
+                    ));
                 }
+            }
+        }
+        Ok(())
+    }
 
-                vec![]
-            })
+    /// Check that the weekend days, if set, are weekday names
+    pub fn validate_weekend_days(&self) -> Result<(), String> {
+        match &self.settings.weekend_days {
+            Some(names) => parse_weekdays(names).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that every effect on events and ranges is a known name
+    pub fn validate_effects(&self) -> Result<(), String> {
+        for (date, detail) in self.date_entries() {
+            parse_effects(&detail.effects).map_err(|e| format!("Effects for {}: {}", date, e))?;
+        }
+        for (idx, range) in self.ranges.iter().enumerate() {
+            parse_effects(&range.effects).map_err(|e| format!("{}: {}", range.label(idx), e))?;
+        }
+        Ok(())
+    }
+
+    /// Check that every event time is a valid `HH:MM`
+    pub fn validate_times(&self) -> Result<(), String> {
+        for (date, detail) in self.date_entries() {
+            if let Some(time) = &detail.time {
+                parse_time(time).map_err(|e| format!("Time for {}: {}", date, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that every alias resolves to a palette color without cycles
+    pub fn validate_aliases(&self) -> Result<(), String> {
+        let mut names: Vec<&String> = self.aliases.keys().collect();
+        names.sort();
+        for name in names {
+            let mut chain = vec![name.as_str()];
+            let mut current = name.as_str();
+            while let Some(next) = self.aliases.get(current) {
+                if chain.contains(&next.as_str()) {
+                    chain.push(next);
+                    return Err(format!("Color alias cycle: {}", chain.join(" -> ")));
+                }
+                chain.push(next);
+                current = next;
+            }
+            if !self.colors.contains_key(current)
+                && ColorPalette::get_color_value(current).is_none()
+            {
+                return Err(format!(
+                    "Color alias '{}' resolves to unknown color '{}'",
+                    name, current
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn validate_colors(&self) -> Result<(), String> {
+        let mut names: Vec<&String> = self.colors.keys().collect();
+        names.sort();
+        for name in names {
+            self.colors[name]
+                .to_color_value()
+                .map_err(|e| format!("Color '{}': {}", name, e))?;
+        }
+        Ok(())
+    }
+
+    /// Add imported events that touch `year`. Config entries on the same
+    /// date take precedence over single-day imports.
+    pub fn import_events(&mut self, events: &[IcsEvent], year: i32) {
+        for event in events {
+            if event.start.year() > year || event.end.year() < year {
+                continue;
+            }
+            if event.start == event.end {
+                self.dates
+                    .entry(event.start.format("%Y-%m-%d").to_string())
+                    .or_insert_with(|| {
+                        vec![RawDateDetail {
+                            description: event.summary.clone(),
+                            color: None,
+                            important: false,
+                            pinned: false,
+                            severity: None,
+                            recurring: None,
+                            time: None,
+                            effects: Vec::new(),
+                            dim_weekends: None,
+                        }]
+                    });
+            } else {
+                self.ranges.push(RawDateRange {
+                    start: event.start.format("%Y-%m-%d").to_string(),
+                    // The importer has already moved DTEND back to the last day
+                    end: event.end.format("%Y-%m-%d").to_string(),
+                    color: IMPORTED_RANGE_COLOR.to_string(),
+                    description: Some(event.summary.clone()),
+                    important: false,
+                    pinned: false,
+                    effects: Vec::new(),
+                    dim_weekends: None,
+                    end_inclusive: true,
+                });
+            }
+        }
+    }
+
+    /// Add events read from a CSV file. Config entries on the same date
+    /// take precedence over single-day imports.
+    pub fn import_csv_events(&mut self, events: &[CsvEvent]) {
+        for event in events {
+            match &event.end_date {
+                None => {
+                    self.dates.entry(event.date.clone()).or_insert_with(|| {
+                        vec![RawDateDetail {
+                            description: event.description.clone(),
+                            color: event.color.clone(),
+                            important: false,
+                            pinned: false,
+                            severity: None,
+                            recurring: None,
+                            time: None,
+                            effects: Vec::new(),
+                            dim_weekends: None,
+                        }]
+                    });
+                }
+                Some(end_date) => self.ranges.push(RawDateRange {
+                    start: event.date.clone(),
+                    end: end_date.clone(),
+                    color: event
+                        .color
+                        .clone()
+                        .unwrap_or_else(|| IMPORTED_RANGE_COLOR.to_string()),
+                    description: Some(event.description.clone()).filter(|d| !d.is_empty()),
+                    important: false,
+                    pinned: false,
+                    effects: Vec::new(),
+                    dim_weekends: None,
+                    end_inclusive: true,
+                }),
+            }
+        }
+    }
+
+    /// Check every inline `#rrggbb` color on events, ranges, and milestones
+    pub fn validate_hex_colors(&self) -> Result<(), String> {
+        let colors = self
+            .date_entries()
+            .into_iter()
+            .filter_map(|(_, detail)| detail.color.as_deref())
+            .chain(self.ranges.iter().map(|range| range.color.as_str()))
+            .chain(
+                self.milestones
+                    .iter()
+                    .filter_map(|milestone| milestone.color.as_deref()),
+            )
+            .chain(self.aliases.values().map(String::as_str))
+            .chain(
+                self.settings
+                    .severity_colors
+                    .iter()
+                    .flatten()
+                    .map(String::as_str),
+            );
+        for color in colors {
+            if color.starts_with('#') {
+                parse_hex_color(color)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Colors on dates, ranges and milestones that are neither a palette
+    /// color, a `[colors]` entry, an alias of one nor hex, each with the
+    /// date or range it's on
+    pub fn unknown_colors(&self) -> Vec<String> {
+        let is_known = |color: &str| {
+            let color = self.resolve_color(color);
+            self.colors.contains_key(&color) || ColorPalette::get_color_value(&color).is_some()
+        };
+        let mut unknown = Vec::new();
+
+        for (date, detail) in self.date_entries() {
+            if let Some(color) = detail.color.as_deref() {
+                if !is_known(color) {
+                    unknown.push(format!("Unknown color '{}' on {}", color, date));
+                }
+            }
+        }
+        for (idx, range) in self.ranges.iter().enumerate() {
+            if !is_known(&range.color) {
+                unknown.push(format!(
+                    "Unknown color '{}' on {}",
+                    range.color,
+                    range.label(idx)
+                ));
+            }
+        }
+        for milestone in &self.milestones {
+            if let Some(color) = milestone.color.as_deref() {
+                if !is_known(color) {
+                    unknown.push(format!(
+                        "Unknown color '{}' on milestone {} ({})",
+                        color, milestone.date, milestone.label
+                    ));
+                }
+            }
+        }
+        unknown
+    }
+
+    /// The `[colors]` table with each entry parsed to RGB
+    pub fn custom_colors(&self) -> HashMap<String, ColorValue> {
+        self.colors
+            .iter()
+            .filter_map(|(name, color)| Some((name.clone(), color.to_color_value().ok()?)))
             .collect()
     }
 
+    /// Follow `[aliases]` from `name` to the color it stands for
+    pub fn resolve_color(&self, name: &str) -> String {
+        let mut current = name;
+        // Bounded so an unvalidated cycle cannot loop forever
+        for _ in 0..=self.aliases.len() {
+            match self.aliases.get(current) {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        current.to_string()
+    }
+
+    pub fn parse_dates(&self) -> HashMap<NaiveDate, Vec<DateDetail>> {
+        let mut details = HashMap::new();
+        for (date_str, detail) in self.date_entries() {
+            if let Ok(date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
+                insert_by_severity(&mut details, date, self.to_detail(detail));
+            }
+        }
+        details
+    }
+
+    /// Each date's events in `year`. Events written for a date come before
+    /// ones that recur onto it, whatever their severity, so the date's own
+    /// entry colors it. Within each group the most severe comes first and
+    /// events of the same severity keep config order.
+    pub fn parse_dates_for_year(&self, year: i32) -> HashMap<NaiveDate, Vec<DateDetail>> {
+        let mut recurring = Vec::new();
+        let mut details = HashMap::new();
+        for (date_str, detail) in self.date_entries() {
+            let Some(date) = parse_date_for_year(date_str, year) else {
+                continue;
+            };
+            if detail.recurring == Some(Recurrence::Yearly) {
+                if let Some(yearly) = yearly_date(year, date.month(), date.day()) {
+                    if yearly != date {
+                        recurring.push((yearly, self.to_detail(detail)));
+                    }
+                }
+            }
+            insert_by_severity(&mut details, date, self.to_detail(detail));
+        }
+        let explicit: HashMap<NaiveDate, usize> = details
+            .iter()
+            .map(|(date, events)| (*date, events.len()))
+            .collect();
+        for (date, detail) in recurring {
+            let skip = explicit.get(&date).copied().unwrap_or(0);
+            insert_by_severity_after(&mut details, date, detail, skip);
+        }
+        details
+    }
+
+    fn to_detail(&self, detail: &RawDateDetail) -> DateDetail {
+        let color = match (&detail.color, detail.severity) {
+            (Some(color), _) => Some(self.resolve_color(color)),
+            (None, Some(severity)) => self.severity_color(severity),
+            (None, None) => None,
+        };
+        DateDetail {
+            description: detail.description.clone(),
+            color,
+            important: detail.important,
+            pinned: detail.pinned,
+            severity: detail.severity,
+            time: detail
+                .time
+                .as_deref()
+                .and_then(|time| parse_time(time).ok()),
+            effects: parse_effects(&detail.effects).unwrap_or_default(),
+            dim_weekends: detail.dim_weekends,
+        }
+    }
+
+    /// The color for a severity from 1 to 5
+    pub fn severity_color(&self, severity: u8) -> Option<String> {
+        let index = usize::from(severity.checked_sub(1)?);
+        match &self.settings.severity_colors {
+            Some(colors) => colors.get(index).map(|c| self.resolve_color(c)),
+            None => DEFAULT_SEVERITY_COLORS.get(index).map(|c| c.to_string()),
+        }
+    }
+
     pub fn parse_ranges(&self) -> Vec<DateRange> {
         self.ranges
             .iter()
             .filter_map(|range| {
                 let start = NaiveDate::parse_from_str(&range.start, "%Y-%m-%d").ok()?;
                 let end = NaiveDate::parse_from_str(&range.end, "%Y-%m-%d").ok()?;
-                Some(DateRange {
-                    start,
-                    end,
-                    color: range.color.clone(),
-                    description: range.description.clone(),
-                })
+                Some(self.to_range(range, start, end))
             })
             .collect()
     }
 
+    /// The range between its parsed bounds. An exclusive end is moved back
+    /// to the last day, so [`DateRange::end`] is always inclusive.
+    fn to_range(&self, range: &RawDateRange, start: NaiveDate, end: NaiveDate) -> DateRange {
+        let end = if range.end_inclusive {
+            end
+        } else {
+            end.pred_opt().unwrap_or(end).max(start)
+        };
+        DateRange {
+            start,
+            end,
+            color: self.resolve_color(&range.color),
+            description: range.description.clone(),
+            important: range.important,
+            pinned: range.pinned,
+            effects: parse_effects(&range.effects).unwrap_or_default(),
+            dim_weekends: range.dim_weekends,
+        }
+    }
+
     pub fn parse_ranges_for_year(&self, year: i32) -> Vec<DateRange> {
         self.ranges
             .iter()
@@ -102,27 +706,85 @@ impl CalendarConfig {
                     NaiveDate::parse_from_str(&range.start, "%Y-%m-%d"),
                     NaiveDate::parse_from_str(&range.end, "%Y-%m-%d"),
                 ) {
-                    return Some(DateRange {
-                        start,
-                        end,
-                        color: range.color.clone(),
-                        description: range.description.clone(),
-                    });
+                    return Some(self.to_range(range, start, end));
                 }
                 if let (Ok(start), Ok(end)) = (
                     NaiveDate::parse_from_str(&format!("{}-{}", year, &range.start), "%Y-%m-%d"),
                     NaiveDate::parse_from_str(&format!("{}-{}", year, &range.end), "%Y-%m-%d"),
                 ) {
-                    return Some(DateRange {
-                        start,
-                        end,
-                        color: range.color.clone(),
-                        description: range.description.clone(),
-                    });
+                    return Some(self.to_range(range, start, end));
                 }
 
                 None
             })
             .collect()
     }
+
+    pub fn parse_milestones_for_year(&self, year: i32) -> Vec<Milestone> {
+        self.milestones
+            .iter()
+            .filter_map(|milestone| {
+                Some(Milestone {
+                    date: parse_date_for_year(&milestone.date, year)?,
+                    label: milestone.label.clone(),
+                    color: milestone.color.as_deref().map(|c| self.resolve_color(c)),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Parse an event time such as `09:30`
+pub fn parse_time(time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time, "%H:%M")
+        .map_err(|_| format!("Invalid time: {} (expected HH:MM)", time))
+}
+
+/// Combine effect names such as "bold" and "underline" into one set
+pub fn parse_effects<S: AsRef<str>>(names: &[S]) -> Result<Effects, String> {
+    names.iter().try_fold(Effects::new(), |effects, name| {
+        let effect = match name.as_ref().to_lowercase().as_str() {
+            "bold" => Effects::BOLD,
+            "italic" => Effects::ITALIC,
+            "underline" => Effects::UNDERLINE,
+            "strikethrough" => Effects::STRIKETHROUGH,
+            _ => {
+                return Err(format!(
+                    "Invalid effect: '{}'. Use bold, italic, underline or strikethrough",
+                    name.as_ref()
+                ))
+            }
+        };
+        Ok(effects | effect)
+    })
+}
+
+/// Parse a weekday and the color to tint it with, e.g. "wed:blue"
+pub fn parse_weekday_color(spec: &str) -> Result<(Weekday, String), String> {
+    let (day, color) = spec
+        .split_once(':')
+        .filter(|(_, color)| !color.trim().is_empty())
+        .ok_or_else(|| {
+            format!(
+                "Invalid weekday highlight: '{}' (expected DAY:COLOR, e.g. wed:blue)",
+                spec
+            )
+        })?;
+    let day = parse_weekdays(&[day])?[0];
+    Ok((day, color.trim().to_string()))
+}
+
+/// Parse weekday names such as "fri" or "saturday", dropping repeats
+pub fn parse_weekdays<S: AsRef<str>>(names: &[S]) -> Result<Vec<Weekday>, String> {
+    let mut days = Vec::new();
+    for name in names {
+        let name = name.as_ref().trim();
+        let day = name
+            .parse::<Weekday>()
+            .map_err(|_| format!("Invalid weekday: {} (expected a name, e.g. sat)", name))?;
+        if !days.contains(&day) {
+            days.push(day);
+        }
+    }
+    Ok(days)
 }
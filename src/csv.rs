@@ -0,0 +1,159 @@
+use crate::config::parse_date_for_year;
+use crate::rendering::parse_hex_color;
+
+/// An event read from a CSV file. Dates are kept as written, either
+/// `YYYY-MM-DD` or a yearly `MM-DD`, like config keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvEvent {
+    pub date: String,
+    pub description: String,
+    pub color: Option<String>,
+    /// Last day of a range, inclusive
+    pub end_date: Option<String>,
+}
+
+/// Columns in the order rows without a header row use
+const COLUMNS: [&str; 4] = ["date", "description", "color", "end_date"];
+
+/// Parse rows of `date,description,color[,end_date]`. A first row naming
+/// the columns is read as a header, and may reorder them. Blank lines and
+/// lines starting with `#` are skipped.
+pub fn parse_csv(contents: &str, delimiter: char) -> Result<Vec<CsvEvent>, String> {
+    let mut events = Vec::new();
+    let mut columns: Option<Vec<String>> = None;
+
+    for (idx, line) in contents.lines().enumerate() {
+        let line_number = idx + 1;
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let fields =
+            split_row(line, delimiter).map_err(|e| format!("Line {}: {}", line_number, e))?;
+
+        if columns.is_none() && is_header(&fields) {
+            columns =
+                Some(parse_header(&fields).map_err(|e| format!("Line {}: {}", line_number, e))?);
+            continue;
+        }
+        let columns =
+            columns.get_or_insert_with(|| COLUMNS.iter().map(|c| c.to_string()).collect());
+        let event =
+            parse_row(columns, fields).map_err(|e| format!("Line {}: {}", line_number, e))?;
+        events.push(event);
+    }
+    Ok(events)
+}
+
+/// A row whose first field isn't a date is taken to name the columns
+fn is_header(fields: &[String]) -> bool {
+    fields
+        .first()
+        .is_some_and(|field| parse_date_for_year(field, 2000).is_none())
+        && fields
+            .iter()
+            .any(|field| field.trim().eq_ignore_ascii_case("date"))
+}
+
+fn parse_header(fields: &[String]) -> Result<Vec<String>, String> {
+    let columns: Vec<String> = fields
+        .iter()
+        .map(|field| field.trim().to_lowercase().replace(['-', ' '], "_"))
+        .collect();
+    for column in &columns {
+        if !COLUMNS.contains(&column.as_str()) {
+            return Err(format!(
+                "Unknown column: '{}'. Use date, description, color and end_date",
+                column
+            ));
+        }
+    }
+    Ok(columns)
+}
+
+fn parse_row(columns: &[String], fields: Vec<String>) -> Result<CsvEvent, String> {
+    if fields.len() > columns.len() {
+        return Err(format!(
+            "expected at most {} fields, got {}",
+            columns.len(),
+            fields.len()
+        ));
+    }
+
+    let mut event = CsvEvent {
+        date: String::new(),
+        description: String::new(),
+        color: None,
+        end_date: None,
+    };
+    for (column, field) in columns.iter().zip(fields) {
+        let field = field.trim().to_string();
+        match column.as_str() {
+            "date" => event.date = field,
+            "description" => event.description = field,
+            "color" if !field.is_empty() => event.color = Some(field),
+            "end_date" if !field.is_empty() => event.end_date = Some(field),
+            _ => {}
+        }
+    }
+
+    if event.date.is_empty() {
+        return Err("missing date".to_string());
+    }
+    let start = parse_date_for_year(&event.date, 2000)
+        .ok_or_else(|| format!("Invalid date: '{}'", event.date))?;
+    if let Some(end_date) = &event.end_date {
+        let end = parse_date_for_year(end_date, 2000)
+            .ok_or_else(|| format!("Invalid end_date: '{}'", end_date))?;
+        // A full date and a yearly MM-DD can't be ordered
+        if is_full_date(end_date) != is_full_date(&event.date) {
+            return Err(format!(
+                "date '{}' and end_date '{}' must both be YYYY-MM-DD or both MM-DD",
+                event.date, end_date
+            ));
+        }
+        if end < start {
+            return Err(format!(
+                "end_date {} is before date {}",
+                end_date, event.date
+            ));
+        }
+    }
+    if let Some(color) = event.color.as_deref().filter(|c| c.starts_with('#')) {
+        parse_hex_color(color)?;
+    }
+    Ok(event)
+}
+
+fn is_full_date(date: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()
+}
+
+/// Split a row on `delimiter`, honoring double-quoted fields with `""`
+/// escapes. Quoted fields can't span lines.
+fn split_row(line: &str, delimiter: char) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    fields.push(field);
+    Ok(fields)
+}
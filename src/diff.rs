@@ -0,0 +1,46 @@
+use crate::models::{Calendar, DayRender};
+
+/// Compare two calendars date-by-date over the filtered range of `new`,
+/// returning one line per date whose color or events changed
+pub fn diff_calendars(old: &Calendar, new: &Calendar) -> Vec<String> {
+    let (start_date, end_date) = new.date_range();
+
+    start_date
+        .iter_days()
+        .take_while(|date| *date <= end_date)
+        .filter_map(|date| diff_day(&old.resolve_day(date), &new.resolve_day(date)))
+        .collect()
+}
+
+fn diff_day(old: &DayRender, new: &DayRender) -> Option<String> {
+    let mut changes = Vec::new();
+
+    if old.color != new.color {
+        changes.push(format!(
+            "color {}→{}",
+            old.color.as_deref().unwrap_or("none"),
+            new.color.as_deref().unwrap_or("none")
+        ));
+    }
+
+    for event in &new.events {
+        if !old.events.contains(event) {
+            changes.push(format!("added \"{}\"", event));
+        }
+    }
+    for event in &old.events {
+        if !new.events.contains(event) {
+            changes.push(format!("removed \"{}\"", event));
+        }
+    }
+
+    if changes.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "{}: {}",
+            new.date.format("%Y-%m-%d"),
+            changes.join(", ")
+        ))
+    }
+}
@@ -50,6 +50,18 @@ impl MonthInfo {
     }
 }
 
+/// English ordinal for a day of the month, e.g. `1st`, `12th`, `22nd`
+pub fn ordinal(day: u32) -> String {
+    let suffix = match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", day, suffix)
+}
+
 const DAYS_IN_WEEK: i64 = 7;
 
 #[derive(Debug, Clone)]
@@ -61,12 +73,14 @@ pub struct WeekLayout {
 }
 
 impl WeekLayout {
+    /// The week of seven days from `start_date`. Days past
+    /// [`NaiveDate::MAX`] repeat it rather than overflow.
     pub fn new(start_date: NaiveDate) -> Self {
         let dates: Vec<NaiveDate> = (0..DAYS_IN_WEEK)
             .map(|day_offset| {
                 start_date
                     .checked_add_signed(chrono::Duration::days(day_offset))
-                    .unwrap()
+                    .unwrap_or(NaiveDate::MAX)
             })
             .collect();
 
@@ -283,3 +297,129 @@ impl BorderState {
         self.before_width + self.after_width
     }
 }
+
+/// Width of `s` as displayed in a terminal, ignoring ANSI escape sequences
+pub fn display_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip a CSI sequence up to and including its final byte
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+/// Cut `s` to at most `width` displayed columns, ending in `…` when it was
+/// cut. Escape sequences are kept, and styling is reset after a cut.
+pub fn truncate_display(s: &str, width: usize) -> String {
+    if display_width(s) <= width {
+        return s.to_string();
+    }
+    let mut output = String::new();
+    let mut shown = 0;
+    let mut styled = false;
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            styled = true;
+            output.push(c);
+            if let Some(next) = chars.next() {
+                output.push(next);
+                if next == '[' {
+                    for c in chars.by_ref() {
+                        output.push(c);
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+            }
+        } else if shown + 1 < width {
+            output.push(c);
+            shown += 1;
+        } else {
+            break;
+        }
+    }
+    if width > 0 {
+        output.push('…');
+    }
+    if styled {
+        output.push_str("\x1b[0m");
+    }
+    output
+}
+
+/// Join `items` with commas, packing as many onto each line as fit in
+/// `width` columns. Lines that continue end with a comma, and an item too
+/// long for a line of its own is cut. Always returns at least one line.
+pub fn wrap_items(items: &[String], width: usize) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    for (idx, item) in items.iter().enumerate() {
+        // Room for the comma that follows, unless this is the last item
+        let comma = usize::from(idx + 1 < items.len());
+        let line = lines.last_mut().unwrap();
+        if line.is_empty() {
+            line.push_str(&truncate_display(item, width.saturating_sub(comma)));
+        } else if display_width(line) + 2 + display_width(item) + comma <= width {
+            line.push_str(", ");
+            line.push_str(item);
+        } else {
+            line.push(',');
+            lines.push(truncate_display(item, width.saturating_sub(comma)));
+        }
+    }
+    lines
+}
+
+/// Pluralize `noun` for `count`, e.g. "1 event" and "3 events"
+pub fn pluralize(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+/// How far `date` is from `today`: "today", "in 5 days" or "2 days ago"
+pub fn relative_days(date: NaiveDate, today: NaiveDate) -> String {
+    let days = (date - today).num_days();
+    match days {
+        0 => "today".to_string(),
+        d if d > 0 => format!("in {}", pluralize(d as usize, "day")),
+        d => format!("{} ago", pluralize(-d as usize, "day")),
+    }
+}
+
+/// A block bar of `width` cells, `fraction` of them filled
+pub fn progress_bar(fraction: f64, width: usize, glyphs: (char, char)) -> (String, String) {
+    let filled = (fraction.clamp(0.0, 1.0) * width as f64).round() as usize;
+    let (full, empty) = glyphs;
+    (
+        full.to_string().repeat(filled),
+        empty.to_string().repeat(width - filled),
+    )
+}
+
+/// Center `s` within `width` columns, measuring it with [`display_width`]
+pub fn center(s: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(s));
+    let left = padding / 2;
+    format!("{}{}{}", " ".repeat(left), s, " ".repeat(padding - left))
+}
+
+/// Julian Day Number of `date`, e.g. 2451545 for 2000-01-01
+pub fn jdn(date: NaiveDate) -> i64 {
+    // Day 1 of the Common Era is JDN 1721426
+    i64::from(date.num_days_from_ce()) + 1_721_425
+}
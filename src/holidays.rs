@@ -0,0 +1,138 @@
+use crate::models::HolidayProvider;
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// Public holidays built in for `--holidays`. Holidays are listed on their
+/// calendar dates; days off in lieu of weekend holidays are not added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum HolidayPreset {
+    /// US federal holidays
+    Us,
+    /// Bank holidays in England and Wales
+    Uk,
+    /// German nationwide holidays
+    De,
+}
+
+impl HolidayPreset {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "us" => Ok(Self::Us),
+            "uk" | "gb" => Ok(Self::Uk),
+            "de" => Ok(Self::De),
+            _ => Err(format!(
+                "Invalid holidays: '{}'. Use one of: us, uk, de",
+                name
+            )),
+        }
+    }
+
+    /// The preset's holidays in `year`, in date order
+    pub fn holidays(self, year: i32) -> Vec<(NaiveDate, String)> {
+        let date = |month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let easter = easter_sunday(year);
+        let after_easter = |days| easter + chrono::Duration::days(days);
+
+        let mut holidays = match self {
+            Self::Us => vec![
+                (date(1, 1), "New Year's Day"),
+                (
+                    nth_weekday(year, 1, Weekday::Mon, 3),
+                    "Martin Luther King Jr. Day",
+                ),
+                (
+                    nth_weekday(year, 2, Weekday::Mon, 3),
+                    "Washington's Birthday",
+                ),
+                (last_weekday(year, 5, Weekday::Mon), "Memorial Day"),
+                (date(7, 4), "Independence Day"),
+                (nth_weekday(year, 9, Weekday::Mon, 1), "Labor Day"),
+                (nth_weekday(year, 10, Weekday::Mon, 2), "Columbus Day"),
+                (date(11, 11), "Veterans Day"),
+                (nth_weekday(year, 11, Weekday::Thu, 4), "Thanksgiving Day"),
+                (date(12, 25), "Christmas Day"),
+            ],
+            Self::Uk => vec![
+                (date(1, 1), "New Year's Day"),
+                (after_easter(-2), "Good Friday"),
+                (after_easter(1), "Easter Monday"),
+                (
+                    nth_weekday(year, 5, Weekday::Mon, 1),
+                    "Early May Bank Holiday",
+                ),
+                (last_weekday(year, 5, Weekday::Mon), "Spring Bank Holiday"),
+                (last_weekday(year, 8, Weekday::Mon), "Summer Bank Holiday"),
+                (date(12, 25), "Christmas Day"),
+                (date(12, 26), "Boxing Day"),
+            ],
+            Self::De => vec![
+                (date(1, 1), "New Year's Day"),
+                (after_easter(-2), "Good Friday"),
+                (after_easter(1), "Easter Monday"),
+                (date(5, 1), "Labour Day"),
+                (after_easter(39), "Ascension Day"),
+                (after_easter(50), "Whit Monday"),
+                (date(10, 3), "German Unity Day"),
+                (date(12, 25), "Christmas Day"),
+                (date(12, 26), "Second Day of Christmas"),
+            ],
+        };
+        // Juneteenth became a federal holiday in 2021
+        if self == Self::Us && year >= 2021 {
+            holidays.push((date(6, 19), "Juneteenth"));
+        }
+
+        let mut holidays: Vec<(NaiveDate, String)> = holidays
+            .into_iter()
+            .map(|(date, name)| (date, name.to_string()))
+            .collect();
+        holidays.sort();
+        holidays
+    }
+
+    pub fn provider(self) -> HolidayProvider {
+        Box::new(move |year| self.holidays(year))
+    }
+}
+
+/// The `n`th (1-based) `weekday` of `month`, e.g. the 4th Thursday of November
+pub fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u8) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n).unwrap()
+}
+
+/// The last `weekday` of `month`, e.g. the last Monday of May
+pub fn last_weekday(year: i32, month: u32, weekday: Weekday) -> NaiveDate {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    let mut date = NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .unwrap()
+        .pred_opt()
+        .unwrap();
+    while date.weekday() != weekday {
+        date = date.pred_opt().unwrap();
+    }
+    date
+}
+
+/// Western (Gregorian) Easter Sunday, by the anonymous Gregorian algorithm
+pub fn easter_sunday(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap()
+}
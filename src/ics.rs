@@ -0,0 +1,150 @@
+use chrono::NaiveDate;
+
+/// An all-day event read from an iCalendar file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcsEvent {
+    pub uid: Option<String>,
+    pub summary: String,
+    pub start: NaiveDate,
+    /// Last day of the event, inclusive
+    pub end: NaiveDate,
+}
+
+/// Properties of a `VEVENT` collected so far, with the line it began on
+#[derive(Default)]
+struct PendingEvent {
+    line: usize,
+    uid: Option<String>,
+    summary: String,
+    start: Option<(NaiveDate, bool)>,
+    end: Option<(NaiveDate, bool)>,
+    /// How many components such as `VALARM` are open inside the event
+    nested: usize,
+}
+
+impl PendingEvent {
+    /// The UID, or the line the event starts on, for error messages
+    fn name(&self) -> String {
+        match &self.uid {
+            Some(uid) => format!("Event {}", uid),
+            None => format!("Event on line {}", self.line),
+        }
+    }
+
+    fn finish(self) -> Result<IcsEvent, String> {
+        let (start, _) = self
+            .start
+            .ok_or_else(|| format!("{}: missing DTSTART", self.name()))?;
+        let end = match self.end {
+            // All-day events end on the day after, exclusive
+            Some((end, true)) => end.pred_opt().unwrap_or(end),
+            Some((end, false)) => end,
+            None => start,
+        };
+        if end < start {
+            return Err(format!("{}: DTEND is before DTSTART", self.name()));
+        }
+        Ok(IcsEvent {
+            uid: self.uid,
+            summary: self.summary,
+            start,
+            end,
+        })
+    }
+}
+
+/// Parse the `VEVENT`s of an iCalendar file. Recurrence rules are ignored,
+/// so only each event's first occurrence is read.
+pub fn parse_ics(contents: &str) -> Result<Vec<IcsEvent>, String> {
+    let mut events = Vec::new();
+    let mut pending: Option<PendingEvent> = None;
+
+    for (line_number, line) in unfold(contents) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Parameters such as `;VALUE=DATE` don't change how values are read
+        let name = name.split(';').next().unwrap_or(name);
+
+        match (name.to_uppercase().as_str(), pending.as_mut()) {
+            ("BEGIN", _) if value.eq_ignore_ascii_case("VEVENT") => {
+                pending = Some(PendingEvent {
+                    line: line_number,
+                    ..PendingEvent::default()
+                });
+            }
+            // Properties of sub-components belong to them, not the event
+            ("BEGIN", Some(event)) => event.nested += 1,
+            ("END", Some(event)) if event.nested > 0 => event.nested -= 1,
+            (_, Some(event)) if event.nested > 0 => {}
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                events.push(pending.take().unwrap().finish()?);
+            }
+            ("UID", Some(event)) => event.uid = Some(value.to_string()),
+            ("SUMMARY", Some(event)) => event.summary = unescape(value),
+            ("DTSTART", Some(event)) => {
+                event.start = Some(parse_date_value(value).map_err(|e| {
+                    format!(
+                        "Line {}: {}: invalid DTSTART: {}",
+                        line_number,
+                        event.name(),
+                        e
+                    )
+                })?);
+            }
+            ("DTEND", Some(event)) => {
+                event.end = Some(parse_date_value(value).map_err(|e| {
+                    format!(
+                        "Line {}: {}: invalid DTEND: {}",
+                        line_number,
+                        event.name(),
+                        e
+                    )
+                })?);
+            }
+            _ => {}
+        }
+    }
+
+    match pending {
+        Some(event) => Err(format!("{}: missing END:VEVENT", event.name())),
+        None => Ok(events),
+    }
+}
+
+/// Join folded lines, keeping each logical line's starting line number
+fn unfold(contents: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (idx, line) in contents.lines().enumerate() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some((_, previous))) => previous.push_str(rest),
+            _ => lines.push((idx + 1, line.to_string())),
+        }
+    }
+    lines
+}
+
+/// A `DATE` or `DATE-TIME` value's date, and whether it was a plain date
+fn parse_date_value(value: &str) -> Result<(NaiveDate, bool), String> {
+    let (date, time) = value.split_at(value.find('T').unwrap_or(value.len()));
+    NaiveDate::parse_from_str(date, "%Y%m%d")
+        .map(|date| (date, time.is_empty()))
+        .map_err(|_| format!("'{}'", value))
+}
+
+fn unescape(text: &str) -> String {
+    let mut output = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => output.push('\n'),
+                Some(other) => output.push(other),
+                None => {}
+            }
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
@@ -1,38 +1,141 @@
 pub mod config;
+pub mod csv;
+pub mod diff;
 pub mod formatting;
+pub mod holidays;
+pub mod ics;
+pub mod locale;
 pub mod models;
+pub mod month_grid;
+pub mod output;
 pub mod rendering;
+#[cfg(feature = "serde")]
+mod serde_fields;
+pub mod terminal;
+#[cfg(feature = "timezone")]
+pub mod timezone;
+#[cfg(feature = "tui")]
+pub mod tui;
 
-use config::CalendarConfig;
+use chrono::Datelike;
+use config::{CalendarConfig, ConfigFormat};
 use models::{Calendar, CalendarOptions};
+use std::collections::HashMap;
 use std::fs;
+use std::io::{self, Read};
 use std::path::PathBuf;
 
-pub fn load_config(config_path: &PathBuf) -> CalendarConfig {
-    if !config_path.exists() {
-        eprintln!(
-            "Config file not found at {:?}, using empty configuration",
-            config_path
-        );
-        return CalendarConfig {
-            dates: Default::default(),
-            ranges: Default::default(),
-        };
+/// The config read when `--config` is not given
+pub const DEFAULT_CONFIG_PATH: &str = "calendar.toml";
+
+/// Load the config at `config_path`, or from stdin when it is `-`. Errors
+/// name the file they come from. The format follows the file extension.
+pub fn load_config(config_path: &PathBuf) -> Result<CalendarConfig, String> {
+    load_config_as(config_path, ConfigFormat::from_path(config_path))
+}
+
+/// Load the config at `config_path`, or from stdin when it is `-`, in `format`
+pub fn load_config_as(
+    config_path: &PathBuf,
+    format: ConfigFormat,
+) -> Result<CalendarConfig, String> {
+    if config_path.as_os_str() == "-" {
+        return load_config_from_stdin(format);
     }
 
-    let contents = fs::read_to_string(config_path).unwrap_or_else(|e| {
-        eprintln!("Failed to read config file {:?}: {}", config_path, e);
-        std::process::exit(1);
-    });
+    let contents = fs::read_to_string(config_path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => format!(
+            "{}: not found (use --config to point elsewhere)",
+            config_path.display()
+        ),
+        _ => format!("{}: {}", config_path.display(), e),
+    })?;
+    parse_config_as(&contents, format).map_err(|e| format!("{}: {}", config_path.display(), e))
+}
 
-    toml::from_str(&contents).unwrap_or_else(|e| {
-        eprintln!("Failed to parse TOML config: {}", e);
-        std::process::exit(1);
-    })
+fn load_config_from_stdin(format: ConfigFormat) -> Result<CalendarConfig, String> {
+    let mut contents = String::new();
+    io::stdin()
+        .read_to_string(&mut contents)
+        .map_err(|e| format!("Failed to read config from stdin: {}", e))?;
+    parse_config_as(&contents, format).map_err(|e| format!("Config from stdin: {}", e))
 }
 
-pub fn build_calendar(year: i32, options: CalendarOptions, config: CalendarConfig) -> Calendar {
-    let details = config.parse_dates_for_year(year);
-    let ranges = config.parse_ranges_for_year(year);
-    Calendar::new(year, options, details, ranges)
+/// Parse and validate the contents of a TOML config file
+pub fn parse_config(contents: &str) -> Result<CalendarConfig, String> {
+    parse_config_as(contents, ConfigFormat::Toml)
+}
+
+/// Parse and validate the contents of a config file in `format`
+pub fn parse_config_as(contents: &str, format: ConfigFormat) -> Result<CalendarConfig, String> {
+    let config: CalendarConfig = match format {
+        ConfigFormat::Toml => {
+            toml::from_str(contents).map_err(|e| format!("Failed to parse TOML config: {}", e))?
+        }
+        #[cfg(feature = "yaml")]
+        ConfigFormat::Yaml => serde_yaml::from_str(contents)
+            .map_err(|e| format!("Failed to parse YAML config: {}", e))?,
+        #[cfg(not(feature = "yaml"))]
+        ConfigFormat::Yaml => {
+            return Err(
+                "YAML configs need the `yaml` feature (cargo install --features yaml)".to_string(),
+            )
+        }
+    };
+    config
+        .validate_aliases()
+        .and_then(|_| config.validate_severities())
+        .and_then(|_| config.validate_times())
+        .and_then(|_| config.validate_effects())
+        .and_then(|_| config.validate_weekend_days())
+        .and_then(|_| config.validate_ranges())
+        .and_then(|_| config.validate_colors())
+        .and_then(|_| config.validate_hex_colors())
+        .map_err(|e| format!("Invalid config: {}", e))?;
+    Ok(config)
+}
+
+pub fn build_calendar(year: i32, mut options: CalendarOptions, config: CalendarConfig) -> Calendar {
+    // A title or subtitle given on the command line takes precedence over
+    // the config
+    if options.title.is_none() {
+        options.title = config.settings.title.clone();
+    }
+    if options.subtitle.is_none() {
+        options.subtitle = config.settings.subtitle.clone();
+    }
+    if options.today_color.is_none() {
+        options.today_color = config.settings.today_color.clone();
+    }
+    if options.weekend_days.is_none() {
+        // Validated when the config was parsed
+        options.weekend_days = config
+            .settings
+            .weekend_days
+            .as_deref()
+            .and_then(|names| config::parse_weekdays(names).ok());
+    }
+    // Windows may run into later years; load their recurring events too
+    let (start_date, end_date) = options.month_filter.get_date_range(year, options.today);
+    let mut details = HashMap::new();
+    let mut ranges = Vec::new();
+    let mut milestones = Vec::new();
+    for year in start_date.year().min(year)..=end_date.year().max(year) {
+        details.extend(config.parse_dates_for_year(year));
+        // Entries with a full date come back for every year; keep one copy
+        for range in config.parse_ranges_for_year(year) {
+            if !ranges.contains(&range) {
+                ranges.push(range);
+            }
+        }
+        for milestone in config.parse_milestones_for_year(year) {
+            if !milestones.contains(&milestone) {
+                milestones.push(milestone);
+            }
+        }
+    }
+    let mut calendar = Calendar::new(year, options, details, ranges, milestones);
+    calendar.colors = config.custom_colors();
+    calendar.legend_labels = config.legend;
+    calendar
 }
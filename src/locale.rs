@@ -0,0 +1,135 @@
+use chrono::Weekday;
+
+/// Languages for month names and weekday abbreviations, chosen with
+/// `--locale`. Annotations stay in English.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Locale {
+    #[default]
+    English,
+    German,
+    French,
+    Spanish,
+    Italian,
+}
+
+/// Widest month name the week rows have room for
+pub const MAX_MONTH_NAME_WIDTH: usize = 9;
+
+impl Locale {
+    /// A language code such as `de`, also with a region or encoding as in
+    /// `de_DE.UTF-8`
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        let language = name
+            .split(['_', '-', '.'])
+            .next()
+            .unwrap_or(name)
+            .to_lowercase();
+        match language.as_str() {
+            "en" | "c" | "posix" => Ok(Self::English),
+            "de" => Ok(Self::German),
+            "fr" => Ok(Self::French),
+            "es" => Ok(Self::Spanish),
+            "it" => Ok(Self::Italian),
+            _ => Err(format!(
+                "Unknown locale: '{}'. Use one of: en, de, fr, es, it",
+                name
+            )),
+        }
+    }
+
+    /// The month's name, at most [`MAX_MONTH_NAME_WIDTH`] characters
+    pub fn month_name(self, month: u32) -> &'static str {
+        let names = match self {
+            Self::English => [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ],
+            Self::German => [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            Self::French => [
+                "Janvier",
+                "Février",
+                "Mars",
+                "Avril",
+                "Mai",
+                "Juin",
+                "Juillet",
+                "Août",
+                "Septembre",
+                "Octobre",
+                "Novembre",
+                "Décembre",
+            ],
+            // "Setiembre" is the accepted spelling that fits the column
+            Self::Spanish => [
+                "Enero",
+                "Febrero",
+                "Marzo",
+                "Abril",
+                "Mayo",
+                "Junio",
+                "Julio",
+                "Agosto",
+                "Setiembre",
+                "Octubre",
+                "Noviembre",
+                "Diciembre",
+            ],
+            Self::Italian => [
+                "Gennaio",
+                "Febbraio",
+                "Marzo",
+                "Aprile",
+                "Maggio",
+                "Giugno",
+                "Luglio",
+                "Agosto",
+                "Settembre",
+                "Ottobre",
+                "Novembre",
+                "Dicembre",
+            ],
+        };
+        month
+            .checked_sub(1)
+            .and_then(|idx| names.get(idx as usize))
+            .copied()
+            .unwrap_or("")
+    }
+
+    /// The weekday's three-character abbreviation, e.g. `Mon` or `Mo.`
+    pub fn weekday(self, day: Weekday) -> &'static str {
+        let names = match self {
+            Self::English => ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+            Self::German => ["Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa.", "So."],
+            Self::French => ["Lun", "Mar", "Mer", "Jeu", "Ven", "Sam", "Dim"],
+            Self::Spanish => ["Lun", "Mar", "Mié", "Jue", "Vie", "Sáb", "Dom"],
+            Self::Italian => ["Lun", "Mar", "Mer", "Gio", "Ven", "Sab", "Dom"],
+        };
+        names[day.num_days_from_monday() as usize]
+    }
+}
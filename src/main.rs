@@ -1,9 +1,19 @@
-use chrono::Datelike;
+use chrono::{Datelike, NaiveDate};
 use clap::Parser;
+use compact_calendar_cli::config::{
+    parse_effects, parse_weekday_color, parse_weekdays, CalendarConfig, ConfigFormat,
+};
+use compact_calendar_cli::holidays::HolidayPreset;
+use compact_calendar_cli::locale::Locale;
 use compact_calendar_cli::models::{
-    CalendarOptions, ColorMode, MonthFilter, PastDateDisplay, WeekStart, WeekendDisplay,
+    AnnotationPosition, AnnotationStyle, BorderStyle, Calendar, CalendarOptions, ColorMode,
+    EventFilter, MonthFilter, PastDateDisplay, TerminalBackground, WeekLabelFormat, WeekNumbering,
+    WeekStart, WeekendDisplay,
 };
-use compact_calendar_cli::rendering::CalendarRenderer;
+use compact_calendar_cli::output::OutputFormat;
+use compact_calendar_cli::rendering::{CalendarRenderer, ColorChoice, ColorPalette, Layout};
+use compact_calendar_cli::DEFAULT_CONFIG_PATH;
+use std::io::Write;
 use std::path::PathBuf;
 
 /// Restore the default SIGPIPE signal handler.
@@ -21,26 +31,78 @@ fn restore_sigpipe_default() {
     }
 }
 
+/// Print `output`, through the pager with `--page` when stdout is a
+/// terminal. Quitting the pager early ends the program quietly.
+fn print_output(output: &[u8], page: bool) {
+    let pager = if page {
+        compact_calendar_cli::terminal::spawn_pager()
+    } else {
+        None
+    };
+    let written = match pager {
+        Some(pager) => {
+            // The pager was started with the default SIGPIPE; ignore it
+            // here so a pager closed early is a write error, not a kill
+            unsafe {
+                libc::signal(libc::SIGPIPE, libc::SIG_IGN);
+            }
+            compact_calendar_cli::terminal::page(pager, output)
+        }
+        None => std::io::stdout().lock().write_all(output),
+    };
+    if let Err(e) = written {
+        eprintln!("Failed to write calendar: {}", e);
+        std::process::exit(1);
+    }
+}
+
 #[derive(Parser, Debug)]
-#[command(version, about)]
+#[command(
+    version,
+    about,
+    after_help = "Options marked [env: ...] can also be set from the environment.\n\
+                  Precedence: command line > environment > built-in default."
+)]
 struct Args {
-    /// Year to display (defaults to current year)
-    #[arg(short, long)]
-    year: Option<i32>,
+    /// Year to display (defaults to current year); repeat to stack several years
+    #[arg(short, long, env = "CALENDAR_YEAR")]
+    year: Vec<i32>,
+
+    /// Years to display one after another, e.g. 2024-2026
+    #[arg(long, value_name = "START-END", conflicts_with = "year")]
+    years: Option<String>,
+
+    /// Path to TOML (or YAML) configuration file with date details, or - to read it from stdin
+    /// (default: calendar.toml, skipped with a warning when missing)
+    #[arg(short, long, env = "CALENDAR_CONFIG")]
+    config: Option<PathBuf>,
 
-    /// Path to TOML configuration file with date details
-    #[arg(short, long, default_value = "calendar.toml")]
-    config: PathBuf,
+    /// Config file format: toml or yaml (default: from the file extension, else toml)
+    #[arg(long, value_name = "FORMAT")]
+    config_format: Option<String>,
 
     /// Week starts on Sunday (default is Monday)
     #[arg(short, long)]
     sunday: bool,
 
+    /// First day of the week, e.g. saturday (default is Monday)
+    #[arg(long, value_name = "DAY", conflicts_with = "sunday")]
+    week_start: Option<String>,
+
+    /// Treat overlapping ranges and unknown color names in the config as
+    /// errors instead of warnings
+    #[arg(long)]
+    strict: bool,
+
     /// Don't dim weekend dates (by default weekends are dimmed)
     #[arg(long)]
     no_dim_weekends: bool,
 
-    /// Work mode: never apply colors to Saturday/Sunday
+    /// Days of the weekend, e.g. fri,sat (overrides `weekend_days` in [settings])
+    #[arg(long, value_name = "DAYS")]
+    weekend: Option<String>,
+
+    /// Work mode: never apply colors to weekend days
     #[arg(short, long)]
     work: bool,
 
@@ -48,36 +110,746 @@ struct Args {
     #[arg(long)]
     no_strikethrough_past: bool,
 
-    /// Display a specific month (number 1-12, name like "march", or "current")
-    #[arg(short = 'm', long)]
+    /// Draw past colored dates in their dimmed shade, to quiet elapsed events
+    #[arg(long)]
+    no_color_past: bool,
+
+    /// Display a specific month (number 1-12, name like "march" or "Mar", or "current")
+    /// or a range within one year like "jan-mar" or "3-6"
+    #[arg(short = 'm', long, env = "CALENDAR_MONTH")]
     month: Option<String>,
 
     /// Display current month plus N additional months (requires --month current)
-    #[arg(short = 'f', long)]
+    #[arg(short = 'f', long, env = "CALENDAR_FOLLOWING_MONTHS")]
     following_months: Option<u32>,
+
+    /// Display only the days from today through the end of the year
+    #[arg(long, conflicts_with_all = ["month", "following_months"])]
+    rest_of_year: bool,
+
+    /// Display one quarter of the year (1-4), e.g. 2 for April through June
+    #[arg(long, value_name = "N", conflicts_with_all = ["month", "following_months", "rest_of_year", "from"])]
+    quarter: Option<u32>,
+
+    /// First day (YYYY-MM-DD) of a custom window to display; requires --to
+    #[arg(long, requires = "to", conflicts_with_all = ["month", "following_months", "rest_of_year"])]
+    from: Option<NaiveDate>,
+
+    /// Last day (YYYY-MM-DD) of a custom window to display; requires --from
+    #[arg(long, requires = "from")]
+    to: Option<NaiveDate>,
+
+    /// Display only ISO weeks N or N-M of the year, e.g. 10-15
+    #[arg(long, value_name = "N-M", conflicts_with_all = ["month", "following_months", "rest_of_year", "quarter", "from"])]
+    weeks: Option<String>,
+
+    /// Show N years side by side as mini month grids, starting at --year
+    #[arg(long, value_name = "N")]
+    year_grid: Option<u32>,
+
+    /// Print the computed grid geometry to stderr (for debugging layout)
+    #[arg(long, hide = true)]
+    explain_layout: bool,
+
+    /// Print a tally of working days, weekend days, events and ranges after the calendar
+    #[arg(long)]
+    count: bool,
+
+    /// Show one free/busy bar per week instead of the grid
+    #[arg(long)]
+    freebusy: bool,
+
+    /// Text layout: wide (a row per week), mini (one --month as a classic block)
+    /// or overview (the year as a grid of classic blocks)
+    #[arg(
+        long,
+        value_name = "LAYOUT",
+        default_value = "wide",
+        conflicts_with = "freebusy"
+    )]
+    layout: String,
+
+    /// Show the whole year as a grid of mini months, like --layout overview
+    #[arg(long, conflicts_with_all = ["freebusy", "layout"])]
+    overview: bool,
+
+    /// Print the builtin colors with their normal and dimmed shades, then exit
+    #[arg(long)]
+    list_colors: bool,
+
+    /// Print the per-day differences between two configuration files
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+    diff: Option<Vec<PathBuf>>,
+
+    /// Output format: text, json, svg, days-csv, tsv, ics, markdown or html (inferred from the --output extension by default)
+    #[arg(long, env = "CALENDAR_FORMAT")]
+    format: Option<String>,
+
+    /// Add the all-day events of an iCalendar (.ics) file to the calendar
+    #[arg(long, value_name = "PATH")]
+    import_ics: Option<PathBuf>,
+
+    /// Add the events of a CSV file with `date,description,color[,end_date]` rows
+    #[arg(long, value_name = "PATH")]
+    import_csv: Option<PathBuf>,
+
+    /// Field delimiter for --import-csv
+    #[arg(long, value_name = "CHAR", default_value_t = ',')]
+    csv_delimiter: char,
+
+    /// Export the configured events and ranges to an iCalendar (.ics) file
+    #[arg(long, value_name = "PATH")]
+    export_ics: Option<PathBuf>,
+
+    /// Print the resolved calendar as JSON (same as --format json)
+    #[arg(long, conflicts_with = "format")]
+    json: bool,
+
+    /// Print the calendar as a Markdown table (same as --format markdown)
+    #[arg(long, conflicts_with_all = ["format", "json"])]
+    markdown: bool,
+
+    /// Print the calendar as an HTML table with inline colors (same as --format html)
+    #[arg(long, conflicts_with_all = ["format", "json", "markdown"])]
+    html: bool,
+
+    /// Print one tab-separated row per day: date, color, description, when
+    /// (past/today/upcoming) and weekend (same as --format tsv)
+    #[arg(long, conflicts_with_all = ["format", "json", "markdown", "html"])]
+    tsv: bool,
+
+    /// Write the rendered calendar to a file instead of stdout; text keeps its
+    /// colors unless NO_COLOR is set or --color never is given
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Draw a titled frame around the calendar with a stats footer
+    #[arg(long)]
+    framed: bool,
+
+    /// Where to place event annotations: right, below or left of the week row
+    #[arg(long, default_value = "right")]
+    annotation_position: String,
+
+    /// How annotation dates are written: short (03/04) or long (March 4th)
+    #[arg(long, default_value = "short")]
+    annotation_style: String,
+
+    /// Week numbers: iso (ISO 8601, Monday starts only) or sequential from the first row
+    #[arg(long, default_value = "iso")]
+    week_numbering: String,
+
+    /// Week label format; {n:02} is the zero-padded week number (drop ":02" to not pad)
+    #[arg(long, default_value = "W{n:02}")]
+    week_label_format: String,
+
+    /// Header title, with {year} for the year (overrides `title` in [settings])
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Subtitle shown under the title (overrides `subtitle` in [settings])
+    #[arg(long)]
+    subtitle: Option<String>,
+
+    /// Background color for today's date (overrides `today_color` in [settings])
+    #[arg(long, value_name = "NAME")]
+    today_color: Option<String>,
+
+    /// Mark public holidays for a locale: us, uk or de (config entries take precedence)
+    #[arg(long, value_name = "LOCALE")]
+    holidays: Option<String>,
+
+    /// Draw only the date grid, without event descriptions
+    #[arg(long)]
+    no_annotations: bool,
+
+    /// List each color used under the calendar, labeled from [legend] in the config
+    #[arg(long)]
+    legend: bool,
+
+    /// Highlight the first and last day of each range with a lighter shade
+    #[arg(long)]
+    range_caps: bool,
+
+    /// Set days colored by a range apart from single events with an effect:
+    /// underline (the default), bold, italic or strikethrough
+    #[arg(long, value_name = "EFFECT", num_args = 0..=1, default_missing_value = "underline")]
+    range_effect: Option<String>,
+
+    /// Mark days that have annotations with a footnote, and key each annotation to its day
+    #[arg(long)]
+    footnotes: bool,
+
+    /// Tint every matching weekday under event and range colors, e.g. wed:blue (repeatable)
+    #[arg(long, value_name = "DAY:COLOR")]
+    highlight_weekday: Vec<String>,
+
+    /// Follow each event's annotation with how far it is from today, e.g. (in 5 days)
+    #[arg(long)]
+    relative: bool,
+
+    /// List weeks from the end of the range back to the start, newest at the top
+    #[arg(long, conflicts_with_all = ["freebusy", "overview"])]
+    reverse: bool,
+
+    /// Skip the weeks of months that have no events or ranges
+    #[arg(long, conflicts_with_all = ["freebusy", "overview"])]
+    only_active: bool,
+
+    /// Language of month names and weekday abbreviations: en, de, fr, es or it
+    #[arg(long, value_name = "LOCALE", env = "CALENDAR_LOCALE")]
+    locale: Option<String>,
+
+    /// Append each range's length in calendar days to its annotation
+    #[arg(long)]
+    range_duration: bool,
+
+    /// Append each range's number of working days to its annotation
+    #[arg(long)]
+    range_workdays: bool,
+
+    /// Shade a cell next to each week number by how many events it has
+    #[arg(long)]
+    week_heat: bool,
+
+    /// Annotate each week with the Julian Day Number of its first day
+    #[arg(long)]
+    jdn_annotate: bool,
+
+    /// Fit lines to N columns (defaults to the terminal width; unlimited when piped)
+    #[arg(long, value_name = "N")]
+    width: Option<usize>,
+
+    /// Wrap annotations onto continuation lines after N columns
+    #[arg(long, value_name = "N")]
+    max_annotation_width: Option<usize>,
+
+    /// Border glyphs: sharp, rounded, double or ascii
+    #[arg(long, value_name = "STYLE", default_value = "sharp")]
+    border_style: String,
+
+    /// Draw borders with plain ASCII (+, -, |), the same as --border-style ascii
+    #[arg(long, conflicts_with = "border_style")]
+    ascii: bool,
+
+    /// Only show date ranges, hiding single-day events
+    #[arg(long)]
+    only_ranges: bool,
+
+    /// Only show single-day events, hiding date ranges
+    #[arg(long)]
+    only_details: bool,
+
+    /// Terminal background: dark, light or auto (queried from the terminal)
+    #[arg(long, default_value = "auto")]
+    bg: String,
+
+    /// When to color output: auto, always or never (auto honors FORCE_COLOR and NO_COLOR)
+    #[arg(long, value_name = "WHEN", default_value = "auto")]
+    color: String,
+
+    /// Print a bar showing how much of the year has passed
+    #[arg(long)]
+    year_progress: bool,
+
+    /// Use this date (YYYY-MM-DD) as today instead of the system clock
+    #[arg(long)]
+    today: Option<NaiveDate>,
+
+    /// Show the output in $PAGER (default: less -R) when stdout is a terminal
+    #[arg(long, conflicts_with = "output")]
+    page: bool,
+
+    /// Browse the calendar interactively, a month at a time
+    #[cfg(feature = "tui")]
+    #[arg(long)]
+    tui: bool,
+
+    /// IANA timezone used to determine today's date (defaults to the system timezone)
+    #[cfg(feature = "timezone")]
+    #[arg(long, env = "CALENDAR_TIMEZONE")]
+    timezone: Option<String>,
+}
+
+#[cfg(feature = "timezone")]
+fn today(args: &Args) -> NaiveDate {
+    if let Some(today) = args.today {
+        return today;
+    }
+    match &args.timezone {
+        Some(name) => {
+            let tz = compact_calendar_cli::timezone::parse_timezone(name).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            compact_calendar_cli::timezone::today_in(tz, chrono::Utc::now())
+        }
+        None => chrono::Local::now().date_naive(),
+    }
+}
+
+#[cfg(not(feature = "timezone"))]
+fn today(args: &Args) -> NaiveDate {
+    args.today
+        .unwrap_or_else(|| chrono::Local::now().date_naive())
+}
+
+/// The years of a `--years` range such as 2024-2026
+fn parse_year_range(range: &str) -> Result<Vec<i32>, String> {
+    let invalid = || format!("Invalid years: '{}' (expected e.g. 2024-2026)", range);
+    let (start, end) = range.split_once('-').ok_or_else(invalid)?;
+    let start: i32 = start.trim().parse().map_err(|_| invalid())?;
+    let end: i32 = end.trim().parse().map_err(|_| invalid())?;
+    if start > end {
+        return Err(format!("Invalid years: '{}' ends before it starts", range));
+    }
+    Ok((start..=end).collect())
+}
+
+fn load_config_or_exit(path: &PathBuf, format: Option<ConfigFormat>) -> CalendarConfig {
+    let format = format.unwrap_or_else(|| ConfigFormat::from_path(path));
+    compact_calendar_cli::load_config_as(path, format).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    })
 }
 
 fn main() {
     restore_sigpipe_default();
     let args = Args::parse();
-    let year = args.year.unwrap_or_else(|| chrono::Local::now().year());
+    let color = ColorChoice::from_name(&args.color).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
 
-    let config = compact_calendar_cli::load_config(&args.config);
+    if args.list_colors {
+        let palette = ColorPalette::new().with_color_choice(color);
+        print!("{}", palette.list_colors_to_string());
+        return;
+    }
+
+    // The only read of the clock: every year and render below shares it
+    let today = today(&args);
+    let years = match &args.years {
+        Some(range) => parse_year_range(range).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }),
+        None if !args.year.is_empty() => args.year.clone(),
+        // A --from window titles the calendar with its own year
+        None => vec![args
+            .from
+            .map(|from| from.year())
+            .unwrap_or_else(|| today.year())],
+    };
+    let year = years[0];
+    let last_year = year.saturating_add(args.year_grid.unwrap_or(1).saturating_sub(1) as i32);
+    let supported = Calendar::supported_years();
+    if let Some(bad) = years
+        .iter()
+        .chain([&last_year])
+        .find(|year| !supported.contains(year))
+    {
+        eprintln!(
+            "Error: Year {} is out of range ({} to {})",
+            bad,
+            supported.start(),
+            supported.end()
+        );
+        std::process::exit(1);
+    }
+    if years.len() > 1 && (args.from.is_some() || args.rest_of_year) {
+        eprintln!("Error: --from and --rest-of-year show a single year");
+        std::process::exit(1);
+    }
+
+    let month_filter = if let (Some(from), Some(to)) = (args.from, args.to) {
+        MonthFilter::from_date_range(from, to).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        })
+    } else if let Some(weeks) = &args.weeks {
+        // Every year shown must have the weeks asked for
+        years
+            .iter()
+            .map(|year| MonthFilter::from_weeks(weeks, *year))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|filters| filters[0].clone())
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            })
+    } else if let Some(quarter) = args.quarter {
+        MonthFilter::from_quarter(quarter).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        })
+    } else if args.rest_of_year {
+        MonthFilter::rest_of_year(year, today).unwrap_or_else(|| {
+            println!("Nothing left to show: {} ended before {}", year, today);
+            std::process::exit(0);
+        })
+    } else {
+        MonthFilter::from_cli_args(args.month.as_deref(), args.following_months).unwrap_or_else(
+            |e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            },
+        )
+    };
+
+    let layout = match &args.layout {
+        _ if args.overview => Layout::Overview,
+        name => Layout::from_name(name).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }),
+    };
+    let mini_month = match (layout, &month_filter) {
+        (Layout::Wide | Layout::Overview, _) => None,
+        (Layout::Mini, MonthFilter::Single(month)) => Some(*month),
+        (Layout::Mini, _) => {
+            eprintln!("Error: --layout mini shows a single month, e.g. --month march");
+            std::process::exit(1);
+        }
+    };
+
+    let background = TerminalBackground::from_bg_flag(&args.bg)
+        .unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        })
+        .unwrap_or_else(|| {
+            // Only ask the terminal when the colored grid is going to it
+            if args.output.is_none() && color.colors_stdout() {
+                compact_calendar_cli::terminal::detect_background()
+            } else {
+                TerminalBackground::Dark
+            }
+        });
 
     let options = CalendarOptions {
-        week_start: WeekStart::from_sunday_flag(args.sunday),
+        week_start: match &args.week_start {
+            Some(name) => WeekStart::from_name(name).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }),
+            None => WeekStart::from_sunday_flag(args.sunday),
+        },
         weekend_display: WeekendDisplay::from_no_dim_flag(args.no_dim_weekends),
         color_mode: ColorMode::from_work_flag(args.work),
-        past_date_display: PastDateDisplay::from_no_strikethrough_flag(args.no_strikethrough_past),
-        month_filter: MonthFilter::from_cli_args(args.month.as_deref(), args.following_months)
+        past_date_display: PastDateDisplay::from_flags(
+            args.no_strikethrough_past,
+            args.no_color_past,
+        ),
+        month_filter,
+        today,
+        framed: args.framed,
+        annotation_position: AnnotationPosition::from_name(&args.annotation_position)
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }),
+        week_label_format: WeekLabelFormat::from_template(&args.week_label_format).unwrap_or_else(
+            |e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            },
+        ),
+        year_progress: args.year_progress,
+        subtitle: args.subtitle.clone(),
+        range_caps: args.range_caps,
+        event_filter: EventFilter::from_only_flags(args.only_ranges, args.only_details)
             .unwrap_or_else(|e| {
                 eprintln!("Error: {}", e);
                 std::process::exit(1);
             }),
+        background,
+        range_duration: args.range_duration,
+        range_workdays: args.range_workdays,
+        jdn_annotate: args.jdn_annotate,
+        week_heat: args.week_heat,
+        annotation_style: AnnotationStyle::from_name(&args.annotation_style).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }),
+        border_style: if args.ascii {
+            BorderStyle::Ascii
+        } else {
+            BorderStyle::from_name(&args.border_style).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            })
+        },
+        week_numbering: WeekNumbering::from_name(&args.week_numbering).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }),
+        width: args.width.or_else(|| {
+            if args.output.is_none() {
+                compact_calendar_cli::terminal::terminal_width()
+            } else {
+                None
+            }
+        }),
+        max_annotation_width: args.max_annotation_width,
+        today_color: args.today_color.clone(),
+        legend: args.legend,
+        hide_annotations: args.no_annotations,
+        holiday_preset: args.holidays.as_deref().map(|name| {
+            HolidayPreset::from_name(name).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            })
+        }),
+        weekend_days: args.weekend.as_deref().map(|days| {
+            let names: Vec<&str> = days.split(',').collect();
+            parse_weekdays(&names).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            })
+        }),
+        range_effect: args.range_effect.as_deref().map(|name| {
+            parse_effects(&[name]).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            })
+        }),
+        footnotes: args.footnotes,
+        locale: args.locale.as_deref().map_or(Locale::English, |name| {
+            Locale::from_name(name).unwrap_or_else(|e| {
+                eprintln!("Warning: {}; using English", e);
+                Locale::English
+            })
+        }),
+        weekday_colors: args
+            .highlight_weekday
+            .iter()
+            .map(|spec| {
+                parse_weekday_color(spec).unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                })
+            })
+            .collect(),
+        relative: args.relative,
+        reverse: args.reverse,
+        title: args.title.clone(),
+        only_active: args.only_active,
     };
 
-    let calendar = compact_calendar_cli::build_calendar(year, options, config);
+    let config_format = args.config_format.as_deref().map(|name| {
+        ConfigFormat::from_name(name).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        })
+    });
+
+    if let Some(paths) = &args.diff {
+        let old = load_config_or_exit(&paths[0], config_format);
+        let new = load_config_or_exit(&paths[1], config_format);
+        let old = compact_calendar_cli::build_calendar(year, options.clone(), old);
+        let new = compact_calendar_cli::build_calendar(year, options, new);
+        for line in compact_calendar_cli::diff::diff_calendars(&old, &new) {
+            println!("{}", line);
+        }
+        return;
+    }
 
-    let renderer = CalendarRenderer::new(&calendar);
-    renderer.render();
+    let mut config = match &args.config {
+        Some(path) => load_config_or_exit(path, config_format),
+        None => {
+            let path = PathBuf::from(DEFAULT_CONFIG_PATH);
+            if path.exists() {
+                load_config_or_exit(&path, config_format)
+            } else {
+                eprintln!(
+                    "Warning: {} not found, showing no events (use --config to point elsewhere)",
+                    DEFAULT_CONFIG_PATH
+                );
+                CalendarConfig::default()
+            }
+        }
+    };
+    if let Some(path) = &args.import_ics {
+        let events = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| compact_calendar_cli::ics::parse_ics(&contents))
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {:?}: {}", path, e);
+                std::process::exit(1);
+            });
+        for &year in &years {
+            config.import_events(&events, year);
+        }
+    }
+    if let Some(path) = &args.import_csv {
+        let events = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| {
+                compact_calendar_cli::csv::parse_csv(&contents, args.csv_delimiter)
+            })
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {:?}: {}", path, e);
+                std::process::exit(1);
+            });
+        config.import_csv_events(&events);
+    }
+    // A misspelled color would otherwise leave its days uncolored
+    for message in config.unknown_colors() {
+        if args.strict {
+            eprintln!("Error: {}", message);
+            std::process::exit(1);
+        }
+        eprintln!("Warning: {}", message);
+    }
+
+    if let Some(years) = args.year_grid {
+        let calendars: Vec<_> = (0..years as i32)
+            .map(|offset| {
+                compact_calendar_cli::build_calendar(year + offset, options.clone(), config.clone())
+            })
+            .collect();
+        print_output(
+            compact_calendar_cli::month_grid::render_year_grid(&calendars, color).as_bytes(),
+            args.page,
+        );
+        return;
+    }
+
+    #[cfg(feature = "tui")]
+    if args.tui {
+        use compact_calendar_cli::tui::{self, NavState};
+        let month = match options.month_filter {
+            MonthFilter::Single(month) => month,
+            _ if year == today.year() => today.month(),
+            _ => 1,
+        };
+        if let Err(e) = tui::run(&config, &options, NavState::new(year, month)) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+    // Recurring events and holidays are expanded for each year separately
+    let calendars: Vec<_> = years
+        .iter()
+        .map(|&year| compact_calendar_cli::build_calendar(year, options.clone(), config.clone()))
+        .collect();
+    let calendar = &calendars[0];
+
+    // Ranges with full dates are in every year's calendar; report them once
+    let mut reported = Vec::new();
+    for calendar in &calendars {
+        for (first, second) in calendar.overlapping_ranges() {
+            let message = format!(
+                "ranges {} and {} overlap; the first one colors the shared days",
+                first.label(),
+                second.label()
+            );
+            if reported.contains(&message) {
+                continue;
+            }
+            if args.strict {
+                eprintln!("Error: {}", message);
+                std::process::exit(1);
+            }
+            eprintln!("Warning: {}", message);
+            reported.push(message);
+        }
+    }
+
+    let format = match &args.format {
+        _ if args.json => OutputFormat::Json,
+        _ if args.markdown => OutputFormat::Markdown,
+        _ if args.html => OutputFormat::Html,
+        _ if args.tsv => OutputFormat::Tsv,
+        Some(name) => OutputFormat::from_name(name).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }),
+        None => args
+            .output
+            .as_deref()
+            .and_then(OutputFormat::from_path)
+            .unwrap_or(OutputFormat::Text),
+    };
+
+    if calendars.len() > 1
+        && (format != OutputFormat::Text || args.output.is_some() || args.export_ics.is_some())
+    {
+        eprintln!("Error: several years can only be shown as text on the terminal");
+        std::process::exit(1);
+    }
+    if mini_month.is_some()
+        && (format != OutputFormat::Text || args.output.is_some() || args.export_ics.is_some())
+    {
+        eprintln!("Error: --layout mini can only be shown as text on the terminal");
+        std::process::exit(1);
+    }
+    if layout == Layout::Overview
+        && (format != OutputFormat::Text || args.output.is_some() || args.export_ics.is_some())
+    {
+        eprintln!("Error: --overview can only be shown as text on the terminal");
+        std::process::exit(1);
+    }
+
+    if let Some(path) = &args.export_ics {
+        if let Err(e) =
+            compact_calendar_cli::output::write_output(calendar, OutputFormat::Ics, false, path)
+        {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(path) = &args.output {
+        let colors = color.colors_file();
+        if let Err(e) = compact_calendar_cli::output::write_output(calendar, format, colors, path) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        if args.count {
+            println!("{}", calendar.count_summary());
+        }
+        return;
+    }
+
+    if args.explain_layout {
+        eprint!("{}", CalendarRenderer::new(calendar).explain_layout());
+    }
+
+    // Rendered whole first, so it can go through the pager
+    let mut output = Vec::new();
+    match format {
+        // Each calendar ends in a blank line, which separates the years
+        OutputFormat::Text => {
+            for calendar in &calendars {
+                let renderer = CalendarRenderer::new(calendar).with_color_choice(color);
+                let written = if args.freebusy {
+                    renderer.render_freebusy_to_writer(&mut output)
+                } else if let Some(month) = mini_month {
+                    renderer.render_mini_to_writer(month, &mut output)
+                } else if layout == Layout::Overview {
+                    renderer.render_overview_to_writer(&mut output)
+                } else {
+                    renderer.render_to_writer(&mut output)
+                };
+                written.expect("writing to a Vec cannot fail");
+                if args.count {
+                    output.extend(format!("{}\n\n", calendar.count_summary()).bytes());
+                }
+            }
+        }
+        _ => {
+            compact_calendar_cli::output::render_to(calendar, format, false, &mut output)
+                .expect("writing to a Vec cannot fail");
+            // Keep stdout to the format itself
+            if args.count {
+                eprintln!("{}", calendar.count_summary());
+            }
+        }
+    }
+    print_output(&output, args.page);
 }
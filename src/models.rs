@@ -1,10 +1,20 @@
-use chrono::{Datelike, NaiveDate};
-use std::collections::HashMap;
+use crate::formatting::{ordinal, pluralize, MonthInfo, WeekLayout};
+use crate::holidays::HolidayPreset;
+use crate::locale::Locale;
+use crate::rendering::ColorValue;
+use anstyle::Effects;
+use chrono::{Datelike, NaiveDate, NaiveTime, Weekday};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum WeekStart {
     Monday,
     Sunday,
+    /// Any other first day, e.g. Saturday
+    Custom(Weekday),
 }
 
 impl WeekStart {
@@ -15,9 +25,42 @@ impl WeekStart {
             Self::Monday
         }
     }
+
+    /// Parse a weekday name such as "saturday" or "sat"
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.parse::<Weekday>() {
+            Ok(Weekday::Mon) => Ok(Self::Monday),
+            Ok(Weekday::Sun) => Ok(Self::Sunday),
+            Ok(day) => Ok(Self::Custom(day)),
+            Err(_) => Err(format!(
+                "Invalid week start: {} (expected a weekday, e.g. saturday)",
+                name
+            )),
+        }
+    }
+
+    pub fn weekday(self) -> Weekday {
+        match self {
+            Self::Monday => Weekday::Mon,
+            Self::Sunday => Weekday::Sun,
+            Self::Custom(day) => day,
+        }
+    }
+
+    /// The days of the week in display order
+    pub fn days(self) -> [Weekday; 7] {
+        let mut day = self.weekday();
+        std::array::from_fn(|_| {
+            let current = day;
+            day = day.succ();
+            current
+        })
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum WeekendDisplay {
     Dimmed,
     Normal,
@@ -34,6 +77,8 @@ impl WeekendDisplay {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ColorMode {
     Normal,
     Work,
@@ -50,27 +95,298 @@ impl ColorMode {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum PastDateDisplay {
     Strikethrough,
     Normal,
+    /// Past colored days in their dimmed shade
+    Dimmed,
+    StrikethroughDimmed,
 }
 
 impl PastDateDisplay {
     pub fn from_no_strikethrough_flag(no_strikethrough: bool) -> Self {
-        if no_strikethrough {
-            Self::Normal
+        Self::from_flags(no_strikethrough, false)
+    }
+
+    /// The display for `--no-strikethrough-past` and `--no-color-past`
+    pub fn from_flags(no_strikethrough: bool, no_color: bool) -> Self {
+        match (no_strikethrough, no_color) {
+            (false, false) => Self::Strikethrough,
+            (true, false) => Self::Normal,
+            (false, true) => Self::StrikethroughDimmed,
+            (true, true) => Self::Dimmed,
+        }
+    }
+
+    pub fn strikes_through(self) -> bool {
+        matches!(self, Self::Strikethrough | Self::StrikethroughDimmed)
+    }
+
+    pub fn dims_color(self) -> bool {
+        matches!(self, Self::Dimmed | Self::StrikethroughDimmed)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TerminalBackground {
+    Dark,
+    Light,
+}
+
+impl TerminalBackground {
+    /// Parse `--bg`; `None` means detect the background automatically
+    pub fn from_bg_flag(bg: &str) -> Result<Option<Self>, String> {
+        match bg.to_lowercase().as_str() {
+            "dark" => Ok(Some(Self::Dark)),
+            "light" => Ok(Some(Self::Light)),
+            "auto" => Ok(None),
+            _ => Err(format!(
+                "Invalid background: {} (expected dark, light or auto)",
+                bg
+            )),
+        }
+    }
+
+    /// Classify a background color by its relative luminance
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        let luminance = 0.2126 * f64::from(r) + 0.7152 * f64::from(g) + 0.0722 * f64::from(b);
+        if luminance > 127.5 {
+            Self::Light
         } else {
-            Self::Strikethrough
+            Self::Dark
+        }
+    }
+
+    /// Whether the terminal's dim effect stays legible on this background
+    pub fn dimming_reads_well(&self) -> bool {
+        *self == Self::Dark
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EventFilter {
+    All,
+    OnlyRanges,
+    OnlyDetails,
+}
+
+impl EventFilter {
+    pub fn from_only_flags(only_ranges: bool, only_details: bool) -> Result<Self, String> {
+        match (only_ranges, only_details) {
+            (false, false) => Ok(Self::All),
+            (true, false) => Ok(Self::OnlyRanges),
+            (false, true) => Ok(Self::OnlyDetails),
+            (true, true) => {
+                Err("--only-ranges and --only-details cannot be used together".to_string())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AnnotationPosition {
+    Right,
+    Below,
+    Left,
+}
+
+impl AnnotationPosition {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "right" => Ok(Self::Right),
+            "below" => Ok(Self::Below),
+            "left" => Ok(Self::Left),
+            _ => Err(format!(
+                "Invalid annotation position: {} (expected right, below or left)",
+                name
+            )),
+        }
+    }
+}
+
+/// How week rows are numbered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum WeekNumbering {
+    /// Counting up from 1 at the first rendered row
+    Sequential,
+    /// ISO 8601 week of the row's first day; needs a Monday week start
+    Iso,
+}
+
+impl WeekNumbering {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "sequential" => Ok(Self::Sequential),
+            "iso" => Ok(Self::Iso),
+            _ => Err(format!(
+                "Invalid week numbering: {} (expected iso or sequential)",
+                name
+            )),
+        }
+    }
+}
+
+/// Which glyphs borders are drawn with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum BorderStyle {
+    /// Box-drawing lines with square corners, `┌─┐`
+    #[default]
+    Sharp,
+    /// Square lines with rounded corners, `╭─╮`
+    Rounded,
+    /// Doubled lines, `╔═╗`
+    Double,
+    /// Plain ASCII, `+-+`
+    Ascii,
+}
+
+impl BorderStyle {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "sharp" => Ok(Self::Sharp),
+            "rounded" => Ok(Self::Rounded),
+            "double" => Ok(Self::Double),
+            "ascii" => Ok(Self::Ascii),
+            _ => Err(format!(
+                "Invalid border style: {} (expected sharp, rounded, double or ascii)",
+                name
+            )),
         }
     }
 }
 
+/// How dates are written in event annotations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AnnotationStyle {
+    /// `03/04`
+    Short,
+    /// `March 4th`
+    Long,
+}
+
+impl AnnotationStyle {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "short" => Ok(Self::Short),
+            "long" => Ok(Self::Long),
+            _ => Err(format!(
+                "Invalid annotation style: {} (expected short or long)",
+                name
+            )),
+        }
+    }
+
+    pub fn format_date(self, date: NaiveDate) -> String {
+        match self {
+            Self::Short => date.format("%m/%d").to_string(),
+            Self::Long => format!(
+                "{} {}",
+                MonthInfo::from_date(date).name,
+                ordinal(date.day())
+            ),
+        }
+    }
+
+    /// Like `format_date`, adding the year when it is not `year`,
+    /// e.g. 01/05/25 or January 5th, 2025
+    pub fn format_date_in(self, date: NaiveDate, year: i32) -> String {
+        if date.year() == year {
+            return self.format_date(date);
+        }
+        match self {
+            Self::Short => date.format("%m/%d/%y").to_string(),
+            Self::Long => format!("{}, {}", self.format_date(date), date.year()),
+        }
+    }
+}
+
+/// Template for the week label in the left column, e.g. `W{n:02}` or `KW{n:02}`
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct WeekLabelFormat {
+    template: String,
+}
+
+impl WeekLabelFormat {
+    /// Highest week number a rendered calendar can reach
+    const MAX_WEEK: i32 = 54;
+
+    pub fn from_template(template: &str) -> Result<Self, String> {
+        if !template.contains("{n}") && !template.contains("{n:02}") {
+            return Err(format!(
+                "Week label format must contain {{n}} or {{n:02}}, got {:?}",
+                template
+            ));
+        }
+        Ok(WeekLabelFormat {
+            template: template.to_string(),
+        })
+    }
+
+    pub fn label(&self, week_num: i32) -> String {
+        self.template
+            .replace("{n:02}", &format!("{:02}", week_num))
+            .replace("{n}", &week_num.to_string())
+    }
+
+    /// Width of the widest label, used to size the left column
+    pub fn width(&self) -> usize {
+        (1..=Self::MAX_WEEK)
+            .map(|week_num| self.label(week_num).chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl TryFrom<String> for WeekLabelFormat {
+    type Error = String;
+
+    fn try_from(template: String) -> Result<Self, String> {
+        Self::from_template(&template)
+    }
+}
+
+impl From<WeekLabelFormat> for String {
+    fn from(format: WeekLabelFormat) -> Self {
+        format.template
+    }
+}
+
+impl Default for WeekLabelFormat {
+    fn default() -> Self {
+        WeekLabelFormat {
+            template: "W{n:02}".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum MonthFilter {
-    All,                       // Default: show all months
-    Single(u32),               // --month N: show specific month (1-12)
-    Current,                   // --month current
-    CurrentWithFollowing(u32), // --month current --following-months N
+    All,                             // Default: show all months
+    Single(u32),                     // --month N: show specific month (1-12)
+    Current,                         // --month current
+    CurrentWithFollowing(u32),       // --month current --following-months N
+    MonthRange(u32, u32),            // --month jan-mar: inclusive run of months
+    Quarter(u32),                    // --quarter N: the three months of quarter N (1-4)
+    DateRange(NaiveDate, NaiveDate), // arbitrary inclusive window of days
+    Weeks(u32, u32),                 // --weeks 10-15: inclusive run of ISO weeks
 }
 
 impl MonthFilter {
@@ -106,13 +422,19 @@ impl MonthFilter {
         }
     }
 
-    /// Parse month from string (number, name, or "current")
+    /// Parse month from string (number, name, "current", or a range like "jan-mar").
+    /// Names and their three-letter abbreviations match in any case.
     fn parse_month(input: &str) -> Result<Self, String> {
+        let input = input.trim();
         // Check for "current" first
         if input.eq_ignore_ascii_case("current") {
             return Ok(MonthFilter::Current);
         }
 
+        if let Some((start, end)) = input.split_once('-') {
+            return Self::parse_month_range(start, end);
+        }
+
         // Try parsing as number
         if let Ok(num) = input.parse::<u32>() {
             return Self::validate_month_number(num);
@@ -122,6 +444,25 @@ impl MonthFilter {
         Self::parse_month_name(input)
     }
 
+    /// Ranges stay within one year; one like "nov-feb" is an error
+    fn parse_month_range(start: &str, end: &str) -> Result<Self, String> {
+        let (MonthFilter::Single(start_month), MonthFilter::Single(end_month)) =
+            (Self::parse_month(start)?, Self::parse_month(end)?)
+        else {
+            return Err(format!(
+                "Invalid month range: '{}-{}'. Use two month numbers or names, e.g. 'jan-mar'",
+                start, end
+            ));
+        };
+        if start_month > end_month {
+            return Err(format!(
+                "Month range '{}-{}' wraps past December; use --from/--to instead",
+                start, end
+            ));
+        }
+        Ok(MonthFilter::MonthRange(start_month, end_month))
+    }
+
     fn validate_month_number(num: u32) -> Result<Self, String> {
         if (1..=12).contains(&num) {
             Ok(MonthFilter::Single(num))
@@ -146,7 +487,7 @@ impl MonthFilter {
             "december" | "dec" => 12,
             _ => {
                 return Err(format!(
-                    "Invalid month: '{}'. Use 1-12, month name (e.g., 'march'), or 'current'",
+                    "Invalid month: '{}'. Use 1-12, a month name or its three-letter abbreviation (e.g. 'march' or 'mar'), or 'current'",
                     input
                 ))
             }
@@ -155,36 +496,132 @@ impl MonthFilter {
         Ok(MonthFilter::Single(month_num))
     }
 
-    /// Get the range of months to display (start_month, end_month) for the given year
-    pub fn get_month_range(&self, _year: i32) -> (u32, u32) {
+    /// The days from `today` through the end of `year`.
+    ///
+    /// A year that has not started yet is shown whole; `None` means the year
+    /// is already over.
+    pub fn rest_of_year(year: i32, today: NaiveDate) -> Option<Self> {
+        match today.year().cmp(&year) {
+            std::cmp::Ordering::Less => Some(MonthFilter::All),
+            std::cmp::Ordering::Equal => Some(MonthFilter::DateRange(
+                today,
+                NaiveDate::from_ymd_opt(year, 12, 31).unwrap(),
+            )),
+            std::cmp::Ordering::Greater => None,
+        }
+    }
+
+    /// The quarter `--quarter` names, e.g. 2 for April through June
+    pub fn from_quarter(quarter: u32) -> Result<Self, String> {
+        if (1..=4).contains(&quarter) {
+            Ok(MonthFilter::Quarter(quarter))
+        } else {
+            Err(format!("Quarter must be 1-4, got {}", quarter))
+        }
+    }
+
+    /// An inclusive window from `--from` to `--to`
+    pub fn from_date_range(from: NaiveDate, to: NaiveDate) -> Result<Self, String> {
+        if from > to {
+            return Err(format!("--from {} is after --to {}", from, to));
+        }
+        Ok(MonthFilter::DateRange(from, to))
+    }
+
+    /// The ISO weeks `--weeks` names in `year`, a single week like "10" or a
+    /// range like "10-15"
+    pub fn from_weeks(input: &str, year: i32) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "Invalid weeks: '{}'. Use a week number or a range, e.g. '10-15'",
+                input
+            )
+        };
+        let (start, end) = input.split_once('-').unwrap_or((input, input));
+        let start: u32 = start.trim().parse().map_err(|_| invalid())?;
+        let end: u32 = end.trim().parse().map_err(|_| invalid())?;
+
+        let last = Self::iso_weeks_in(year);
+        if let Some(week) = [start, end].into_iter().find(|week| *week == 0) {
+            return Err(format!("Week numbers start at 1, got {}", week));
+        }
+        if let Some(week) = [start, end].into_iter().find(|week| *week > last) {
+            return Err(format!(
+                "{} has {} ISO weeks, got week {}",
+                year, last, week
+            ));
+        }
+        if start > end {
+            return Err(format!("Week range '{}' runs backwards", input));
+        }
+        Ok(MonthFilter::Weeks(start, end))
+    }
+
+    /// How many ISO weeks `year` has, 52 or 53
+    pub fn iso_weeks_in(year: i32) -> u32 {
+        // December 28th always falls in the last ISO week
+        NaiveDate::from_ymd_opt(year, 12, 28)
+            .unwrap()
+            .iso_week()
+            .week()
+    }
+
+    /// The Monday of ISO week `start` through the Sunday of ISO week `end`.
+    /// Weeks past the end of `year` are clamped to its last week.
+    fn iso_week_bounds(year: i32, start: u32, end: u32) -> (NaiveDate, NaiveDate) {
+        let last = Self::iso_weeks_in(year);
+        let day = |week: u32, weekday| {
+            NaiveDate::from_isoywd_opt(year, week.clamp(1, last), weekday).unwrap()
+        };
+        (day(start, Weekday::Mon), day(end, Weekday::Sun))
+    }
+
+    /// Get the range of months of `year` to display (start_month, end_month)
+    /// relative to `today`
+    pub fn get_month_range(&self, year: i32, today: NaiveDate) -> (u32, u32) {
         match self {
             MonthFilter::All => (1, 12),
             MonthFilter::Single(m) => (*m, *m),
-            MonthFilter::Current => {
-                let month = Self::get_current_month_number();
-                (month, month)
-            }
+            MonthFilter::Current => (today.month(), today.month()),
             MonthFilter::CurrentWithFollowing(n) => {
-                let start_month = Self::get_current_month_number();
+                let start_month = today.month();
                 let end_month = (start_month + n).min(12);
                 (start_month, end_month)
             }
+            MonthFilter::MonthRange(start, end) => (*start, *end),
+            MonthFilter::Quarter(q) => (q * 3 - 2, q * 3),
+            MonthFilter::DateRange(start, end) => (start.month(), end.month()),
+            // Weeks reaching into the years around stop at January and December
+            MonthFilter::Weeks(start, end) => {
+                let (from, to) = Self::iso_week_bounds(year, *start, *end);
+                let start_month = if from.year() < year { 1 } else { from.month() };
+                let end_month = if to.year() > year { 12 } else { to.month() };
+                (start_month, end_month)
+            }
         }
     }
 
-    fn get_current_month_number() -> u32 {
-        chrono::Local::now().date_naive().month()
-    }
-
-    /// Check if a specific month should be displayed
-    pub fn should_display_month(&self, month: u32, year: i32) -> bool {
-        let (start, end) = self.get_month_range(year);
-        month >= start && month <= end
+    /// Check if a specific month of `year` should be displayed
+    pub fn should_display_month(&self, month: u32, year: i32, today: NaiveDate) -> bool {
+        let (start, end) = self.get_month_range(year, today);
+        match self {
+            // A window reaching into the next year wraps around December
+            MonthFilter::DateRange(from, to) if from.year() != to.year() => {
+                to.year() - from.year() > 1 || month >= start || month <= end
+            }
+            _ => month >= start && month <= end,
+        }
     }
 
     /// Get the filtered date range (start_date, end_date) for rendering
-    pub fn get_date_range(&self, year: i32) -> (NaiveDate, NaiveDate) {
-        let (start_month, end_month) = self.get_month_range(year);
+    pub fn get_date_range(&self, year: i32, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            MonthFilter::DateRange(start, end) => return (*start, *end),
+            MonthFilter::Weeks(start, end) => return Self::iso_week_bounds(year, *start, *end),
+            _ => {}
+        }
+
+        let (start_month, end_month) = self.get_month_range(year, today);
 
         let start_date = NaiveDate::from_ymd_opt(year, start_month, 1).unwrap();
         let end_date = Self::get_last_day_of_month(year, end_month);
@@ -192,6 +629,18 @@ impl MonthFilter {
         (start_date, end_date)
     }
 
+    /// Whether the week row starting on `first_day` is shown. A row belongs
+    /// to the ISO week holding most of its days, i.e. its fourth day.
+    pub fn should_render_week(&self, year: i32, first_day: NaiveDate) -> bool {
+        match self {
+            MonthFilter::Weeks(start, end) => first_day
+                .checked_add_signed(chrono::Duration::days(3))
+                .map(|day| day.iso_week())
+                .is_some_and(|week| week.year() == year && (*start..=*end).contains(&week.week())),
+            _ => true,
+        }
+    }
+
     fn get_last_day_of_month(year: i32, month: u32) -> NaiveDate {
         if month == 12 {
             NaiveDate::from_ymd_opt(year, 12, 31).unwrap()
@@ -206,28 +655,217 @@ impl MonthFilter {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DateDetail {
     pub description: String,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub color: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub important: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pinned: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub severity: Option<u8>,
+    /// Start time, shown between the date and description
+    #[cfg_attr(feature = "serde", serde(default, with = "crate::serde_fields::time"))]
+    pub time: Option<NaiveTime>,
+    /// Added to the day's cell and annotation, with today's and past effects
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::serde_fields::effects")
+    )]
+    pub effects: Effects,
+    /// Whether the color dims on weekends; `None` follows the weekend display
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dim_weekends: Option<bool>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DateRange {
     pub start: NaiveDate,
     pub end: NaiveDate,
     pub color: String,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub description: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub important: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pinned: bool,
+    /// Added to every day's cell and the annotation
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::serde_fields::effects")
+    )]
+    pub effects: Effects,
+    /// Whether the color dims on weekends; `None` follows the weekend display
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dim_weekends: Option<bool>,
+}
+
+impl DateRange {
+    /// Calendar days covered by the range, inclusive of both ends
+    pub fn duration_days(&self) -> i64 {
+        (self.end - self.start).num_days() + 1
+    }
+
+    /// Whether the two ranges share at least one day
+    pub fn overlaps(&self, other: &DateRange) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// The range's dates and description, for messages
+    pub fn label(&self) -> String {
+        match &self.description {
+            Some(desc) => format!("{} to {} ({})", self.start, self.end, desc),
+            None => format!("{} to {}", self.start, self.end),
+        }
+    }
+}
+
+/// Background used for milestones that don't set their own color
+pub const DEFAULT_MILESTONE_COLOR: &str = "orange";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Milestone {
+    pub date: NaiveDate,
+    pub label: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub color: Option<String>,
+}
+
+impl Milestone {
+    pub fn color(&self) -> &str {
+        self.color.as_deref().unwrap_or(DEFAULT_MILESTONE_COLOR)
+    }
+
+    /// Describe how far `today` is from this milestone, e.g. "14 days to Launch"
+    pub fn countdown(&self, today: NaiveDate) -> String {
+        let days = (self.date - today).num_days();
+        match days {
+            0 => format!("{} today", self.label),
+            1 => format!("1 day to {}", self.label),
+            -1 => format!("{} done (1 day ago)", self.label),
+            d if d > 0 => format!("{} days to {}", d, self.label),
+            d => format!("{} done ({} days ago)", self.label, -d),
+        }
+    }
+}
+
+/// Where a date's color comes from, in order of precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColorSource {
+    Today,
+    Milestone,
+    Detail,
+    Range,
+    Weekday,
+}
+
+/// Counts of what falls within the rendered date range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarStats {
+    pub events: usize,
+    pub ranges: usize,
+    /// Days covered by at least one range
+    pub range_days: usize,
+    pub weekend_days: usize,
+    /// Business days; in work mode, colored days are not counted either
+    pub workdays: usize,
+}
+
+/// Everything resolved for a single date: its color and the events on it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayRender {
+    pub date: NaiveDate,
+    pub color: Option<String>,
+    pub events: Vec<String>,
+}
+
+/// The events and ranges on a single date, from [`Calendar::events_on`]
+#[derive(Debug, Clone)]
+pub struct EventsOnDate<'a> {
+    pub date: NaiveDate,
+    /// The date's events, in config order; empty when it has none
+    pub details: &'a [DateDetail],
+    /// Every range covering the date, in config order
+    pub ranges: Vec<&'a DateRange>,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CalendarOptions {
     pub week_start: WeekStart,
     pub weekend_display: WeekendDisplay,
     pub color_mode: ColorMode,
     pub past_date_display: PastDateDisplay,
     pub month_filter: MonthFilter,
+    /// The one reference date for today's color, past dates and countdowns.
+    /// Renderers never read the clock, so a render can't straddle midnight.
+    pub today: NaiveDate,
+    pub framed: bool,
+    pub annotation_position: AnnotationPosition,
+    pub week_label_format: WeekLabelFormat,
+    pub year_progress: bool,
+    pub subtitle: Option<String>,
+    pub range_caps: bool,
+    pub event_filter: EventFilter,
+    pub background: TerminalBackground,
+    pub range_duration: bool,
+    pub range_workdays: bool,
+    pub jdn_annotate: bool,
+    pub week_heat: bool,
+    pub annotation_style: AnnotationStyle,
+    pub border_style: BorderStyle,
+    pub week_numbering: WeekNumbering,
+    /// Columns available for each line; longer annotations wrap
+    pub width: Option<usize>,
+    /// Wrap annotations at this many columns, regardless of `width`
+    pub max_annotation_width: Option<usize>,
+    /// Background for today, over any color the date would otherwise have
+    pub today_color: Option<String>,
+    /// List the colors in use under the calendar
+    pub legend: bool,
+    /// Draw only the grid, without event descriptions beside it
+    pub hide_annotations: bool,
+    /// Built-in public holidays to merge in under config entries
+    pub holiday_preset: Option<HolidayPreset>,
+    /// Days dimmed as the weekend; `None` uses the config, then Saturday and Sunday
+    pub weekend_days: Option<Vec<Weekday>>,
+    /// Extra effect for days colored by a range, setting them apart from events
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::serde_fields::effects::option")
+    )]
+    pub range_effect: Option<Effects>,
+    /// Mark days that have annotations, and key each annotation to its day
+    pub footnotes: bool,
+    /// Language of month names and weekday abbreviations
+    pub locale: Locale,
+    /// Colors for every matching weekday, under event and range colors
+    pub weekday_colors: Vec<(Weekday, String)>,
+    /// Follow each event's annotation with how far it is from today
+    pub relative: bool,
+    /// Draw the weeks newest first
+    pub reverse: bool,
+    /// Header title in place of `COMPACT CALENDAR {year}`; `{year}` is
+    /// replaced by the year
+    pub title: Option<String>,
+    /// Skip the weeks of months with no events or ranges
+    pub only_active: bool,
 }
 
+/// Supplies holidays, as `(date, name)` pairs, for a given year
+pub type HolidayProvider = Box<dyn Fn(i32) -> Vec<(NaiveDate, String)>>;
+
+/// Color given to holidays that have no detail of their own in the config
+pub const DEFAULT_HOLIDAY_COLOR: &str = "red";
+
+/// The weekend unless configured otherwise
+pub const DEFAULT_WEEKEND_DAYS: [Weekday; 2] = [Weekday::Sat, Weekday::Sun];
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Calendar {
     pub year: i32,
     pub week_start: WeekStart,
@@ -235,33 +873,723 @@ pub struct Calendar {
     pub color_mode: ColorMode,
     pub past_date_display: PastDateDisplay,
     pub month_filter: MonthFilter,
-    pub details: HashMap<NaiveDate, DateDetail>,
+    pub today: NaiveDate,
+    pub framed: bool,
+    pub annotation_position: AnnotationPosition,
+    pub week_label_format: WeekLabelFormat,
+    pub year_progress: bool,
+    pub subtitle: Option<String>,
+    pub range_caps: bool,
+    pub event_filter: EventFilter,
+    pub background: TerminalBackground,
+    pub range_duration: bool,
+    pub range_workdays: bool,
+    pub jdn_annotate: bool,
+    pub week_heat: bool,
+    pub annotation_style: AnnotationStyle,
+    pub border_style: BorderStyle,
+    pub week_numbering: WeekNumbering,
+    pub width: Option<usize>,
+    pub max_annotation_width: Option<usize>,
+    pub today_color: Option<String>,
+    pub legend: bool,
+    pub hide_annotations: bool,
+    pub weekend_days: Vec<Weekday>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::serde_fields::effects::option")
+    )]
+    pub range_effect: Option<Effects>,
+    pub footnotes: bool,
+    pub locale: Locale,
+    pub weekday_colors: Vec<(Weekday, String)>,
+    pub relative: bool,
+    pub reverse: bool,
+    pub title: Option<String>,
+    pub only_active: bool,
+    /// Colors from the config's `[colors]` table
+    pub colors: HashMap<String, ColorValue>,
+    /// Labels for colors from the config's `[legend]` table
+    pub legend_labels: HashMap<String, String>,
+    pub holidays: HashSet<NaiveDate>,
+    /// Each date's events, in the order they are listed
+    pub details: HashMap<NaiveDate, Vec<DateDetail>>,
     pub ranges: Vec<DateRange>,
+    pub milestones: Vec<Milestone>,
 }
 
-impl Calendar {
-    pub fn new(
-        year: i32,
-        options: CalendarOptions,
-        details: HashMap<NaiveDate, DateDetail>,
-        ranges: Vec<DateRange>,
-    ) -> Self {
-        Calendar {
+impl Default for CalendarOptions {
+    /// The command line's defaults, with today from the system clock
+    fn default() -> Self {
+        CalendarOptions {
+            week_start: WeekStart::Monday,
+            weekend_display: WeekendDisplay::Dimmed,
+            color_mode: ColorMode::Normal,
+            past_date_display: PastDateDisplay::Strikethrough,
+            month_filter: MonthFilter::All,
+            today: chrono::Local::now().date_naive(),
+            framed: false,
+            annotation_position: AnnotationPosition::Right,
+            week_label_format: WeekLabelFormat::default(),
+            year_progress: false,
+            subtitle: None,
+            range_caps: false,
+            event_filter: EventFilter::All,
+            background: TerminalBackground::Dark,
+            range_duration: false,
+            range_workdays: false,
+            jdn_annotate: false,
+            week_heat: false,
+            annotation_style: AnnotationStyle::Short,
+            border_style: BorderStyle::Sharp,
+            week_numbering: WeekNumbering::Iso,
+            width: None,
+            max_annotation_width: None,
+            today_color: None,
+            legend: false,
+            hide_annotations: false,
+            holiday_preset: None,
+            weekend_days: None,
+            range_effect: None,
+            footnotes: false,
+            locale: Locale::English,
+            weekday_colors: Vec::new(),
+            relative: false,
+            reverse: false,
+            title: None,
+            only_active: false,
+        }
+    }
+}
+
+/// Builds a [`Calendar`] in code, without a config file
+#[derive(Debug, Clone, Default)]
+pub struct CalendarBuilder {
+    year: Option<i32>,
+    options: CalendarOptions,
+    details: HashMap<NaiveDate, Vec<DateDetail>>,
+    ranges: Vec<DateRange>,
+    milestones: Vec<Milestone>,
+    colors: HashMap<String, ColorValue>,
+}
+
+impl CalendarBuilder {
+    /// A builder with the command line's defaults, for the current year
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn year(mut self, year: i32) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    /// Replace every display option at once
+    pub fn options(mut self, options: CalendarOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn week_start(mut self, week_start: WeekStart) -> Self {
+        self.options.week_start = week_start;
+        self
+    }
+
+    pub fn month_filter(mut self, month_filter: MonthFilter) -> Self {
+        self.options.month_filter = month_filter;
+        self
+    }
+
+    pub fn today(mut self, today: NaiveDate) -> Self {
+        self.options.today = today;
+        self
+    }
+
+    /// Tint every `day` with `color`, under event and range colors
+    pub fn highlight_weekday(mut self, day: Weekday, color: &str) -> Self {
+        self.options.weekday_colors.push((day, color.to_string()));
+        self
+    }
+
+    pub fn weekend_days(mut self, days: Vec<Weekday>) -> Self {
+        self.options.weekend_days = Some(days);
+        self
+    }
+
+    pub fn weekend_display(mut self, weekend_display: WeekendDisplay) -> Self {
+        self.options.weekend_display = weekend_display;
+        self
+    }
+
+    pub fn color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.options.color_mode = color_mode;
+        self
+    }
+
+    pub fn past_date_display(mut self, past_date_display: PastDateDisplay) -> Self {
+        self.options.past_date_display = past_date_display;
+        self
+    }
+
+    pub fn annotation_position(mut self, annotation_position: AnnotationPosition) -> Self {
+        self.options.annotation_position = annotation_position;
+        self
+    }
+
+    pub fn event_filter(mut self, event_filter: EventFilter) -> Self {
+        self.options.event_filter = event_filter;
+        self
+    }
+
+    /// Add an event on `date`, after any already there
+    pub fn add_detail(mut self, date: NaiveDate, detail: DateDetail) -> Self {
+        self.details.entry(date).or_default().push(detail);
+        self
+    }
+
+    pub fn add_range(mut self, range: DateRange) -> Self {
+        self.ranges.push(range);
+        self
+    }
+
+    pub fn add_milestone(mut self, milestone: Milestone) -> Self {
+        self.milestones.push(milestone);
+        self
+    }
+
+    /// Define a color usable by name, replacing a builtin of the same name
+    pub fn add_color(mut self, name: &str, color: impl Into<ColorValue>) -> Self {
+        self.colors.insert(name.to_string(), color.into());
+        self
+    }
+
+    pub fn details(mut self, details: HashMap<NaiveDate, Vec<DateDetail>>) -> Self {
+        self.details = details;
+        self
+    }
+
+    pub fn ranges(mut self, ranges: Vec<DateRange>) -> Self {
+        self.ranges = ranges;
+        self
+    }
+
+    pub fn milestones(mut self, milestones: Vec<Milestone>) -> Self {
+        self.milestones = milestones;
+        self
+    }
+
+    pub fn build(self) -> Calendar {
+        let CalendarBuilder {
+            year,
+            options,
+            mut details,
+            mut ranges,
+            milestones,
+            colors,
+        } = self;
+        let year = year.unwrap_or_else(|| options.today.year());
+        let holiday_preset = options.holiday_preset;
+
+        // Drop the hidden category up front so coloring, annotations and
+        // exports all agree on what is shown
+        match options.event_filter {
+            EventFilter::All => {}
+            EventFilter::OnlyRanges => details.clear(),
+            EventFilter::OnlyDetails => ranges.clear(),
+        }
+
+        let mut calendar = Calendar {
             year,
             week_start: options.week_start,
             weekend_display: options.weekend_display,
             color_mode: options.color_mode,
             past_date_display: options.past_date_display,
             month_filter: options.month_filter,
+            today: options.today,
+            framed: options.framed,
+            annotation_position: options.annotation_position,
+            week_label_format: options.week_label_format,
+            year_progress: options.year_progress,
+            subtitle: options.subtitle,
+            range_caps: options.range_caps,
+            event_filter: options.event_filter,
+            background: options.background,
+            range_duration: options.range_duration,
+            range_workdays: options.range_workdays,
+            jdn_annotate: options.jdn_annotate,
+            week_heat: options.week_heat,
+            annotation_style: options.annotation_style,
+            border_style: options.border_style,
+            week_numbering: options.week_numbering,
+            width: options.width,
+            max_annotation_width: options.max_annotation_width,
+            today_color: options.today_color,
+            legend: options.legend,
+            hide_annotations: options.hide_annotations,
+            weekend_days: options
+                .weekend_days
+                .unwrap_or_else(|| DEFAULT_WEEKEND_DAYS.to_vec()),
+            range_effect: options.range_effect,
+            footnotes: options.footnotes,
+            locale: options.locale,
+            weekday_colors: options.weekday_colors,
+            relative: options.relative,
+            reverse: options.reverse,
+            title: options.title,
+            only_active: options.only_active,
+            colors,
+            legend_labels: HashMap::new(),
+            holidays: HashSet::new(),
             details,
             ranges,
+            milestones,
+        };
+        if let Some(preset) = holiday_preset {
+            calendar.set_holiday_provider(preset.provider());
         }
+        calendar
+    }
+}
+
+impl Calendar {
+    pub fn new(
+        year: i32,
+        options: CalendarOptions,
+        details: HashMap<NaiveDate, Vec<DateDetail>>,
+        ranges: Vec<DateRange>,
+        milestones: Vec<Milestone>,
+    ) -> Self {
+        CalendarBuilder::new()
+            .year(year)
+            .options(options)
+            .details(details)
+            .ranges(ranges)
+            .milestones(milestones)
+            .build()
+    }
+
+    /// Merge the holidays `provider` returns for this year into the calendar.
+    ///
+    /// Holidays show up like config details, which take precedence when both
+    /// exist for a date, and are excluded from business days.
+    pub fn set_holiday_provider(&mut self, provider: HolidayProvider) {
+        for (date, name) in provider(self.year) {
+            if date.year() != self.year {
+                continue;
+            }
+            self.holidays.insert(date);
+            if self.event_filter != EventFilter::OnlyRanges {
+                self.details.entry(date).or_insert_with(|| {
+                    vec![DateDetail {
+                        description: name,
+                        color: Some(DEFAULT_HOLIDAY_COLOR.to_string()),
+                        important: false,
+                        pinned: false,
+                        severity: None,
+                        time: None,
+                        effects: Effects::new(),
+                        dim_weekends: None,
+                    }]
+                });
+            }
+        }
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.contains(&date)
+    }
+
+    /// A weekday that is not a holiday
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    /// Business days covered by `range`
+    pub fn range_workdays(&self, range: &DateRange) -> usize {
+        range
+            .start
+            .iter_days()
+            .take_while(|date| *date <= range.end)
+            .filter(|date| self.is_business_day(*date))
+            .count()
     }
 
     pub fn get_weekday_num(&self, date: NaiveDate) -> u32 {
-        match self.week_start {
-            WeekStart::Monday => date.weekday().num_days_from_monday(),
-            WeekStart::Sunday => date.weekday().num_days_from_sunday(),
+        let start = self.week_start.weekday().num_days_from_monday();
+        (date.weekday().num_days_from_monday() + 7 - start) % 7
+    }
+
+    /// Get the filtered date range (start_date, end_date) to render
+    pub fn date_range(&self) -> (NaiveDate, NaiveDate) {
+        self.month_filter.get_date_range(self.year, self.today)
+    }
+
+    /// Pairs of ranges sharing days, in config order. The first of each
+    /// pair colors the days they share.
+    pub fn overlapping_ranges(&self) -> Vec<(&DateRange, &DateRange)> {
+        let mut overlaps = Vec::new();
+        for (idx, first) in self.ranges.iter().enumerate() {
+            for second in &self.ranges[idx + 1..] {
+                if first.overlaps(second) {
+                    overlaps.push((first, second));
+                }
+            }
+        }
+        overlaps
+    }
+
+    /// Years whose weeks all lie within the dates chrono supports. Nearer
+    /// the bounds, [`Calendar::week_start_of`] and [`WeekLayout::new`]
+    /// saturate instead of completing the first and last weeks.
+    pub fn supported_years() -> std::ops::RangeInclusive<i32> {
+        NaiveDate::MIN.year() + 1..=NaiveDate::MAX.year() - 1
+    }
+
+    /// The first day of the week holding `date`, or [`NaiveDate::MIN`] when
+    /// that day is before the earliest date chrono supports
+    pub fn week_start_of(&self, date: NaiveDate) -> NaiveDate {
+        date.checked_sub_signed(chrono::Duration::days(i64::from(
+            self.get_weekday_num(date),
+        )))
+        .unwrap_or(NaiveDate::MIN)
+    }
+
+    /// The weeks covering the filtered date range, in the order they are
+    /// rendered, for building other views on top of the calendar
+    pub fn weeks(&self) -> impl Iterator<Item = WeekLayout> {
+        self.weeks_for(&self.month_filter)
+    }
+
+    /// The weeks covering `filter`'s date range, each starting on the first
+    /// day of the week
+    pub fn weeks_for(&self, filter: &MonthFilter) -> impl Iterator<Item = WeekLayout> {
+        let (start_date, end_date) = filter.get_date_range(self.year, self.today);
+        let (year, filter) = (self.year, filter.clone());
+        let active_months = self.only_active.then(|| self.active_months());
+        std::iter::successors(Some(self.week_start_of(start_date)), |date| {
+            date.checked_add_signed(chrono::Duration::days(7))
+        })
+        .take_while(move |date| *date <= end_date)
+        .filter(move |date| filter.should_render_week(year, *date))
+        .map(WeekLayout::new)
+        .filter(move |layout| {
+            // A week touching an active month is kept whole
+            active_months.as_ref().is_none_or(|months| {
+                layout
+                    .dates
+                    .iter()
+                    .any(|date| months.contains(&(date.year(), date.month())))
+            })
+        })
+    }
+
+    /// The (year, month) pairs holding an event or overlapping a range
+    fn active_months(&self) -> HashSet<(i32, u32)> {
+        let mut months: HashSet<(i32, u32)> = self
+            .details
+            .keys()
+            .map(|date| (date.year(), date.month()))
+            .collect();
+        for range in &self.ranges {
+            let mut month = (range.start.year(), range.start.month());
+            while month <= (range.end.year(), range.end.month()) {
+                months.insert(month);
+                month = match month {
+                    (year, 12) => (year + 1, 1),
+                    (year, month) => (year, month + 1),
+                };
+            }
+        }
+        months
+    }
+
+    /// The number shown for a week row: the ISO week of its first day when
+    /// numbering by ISO weeks on a Monday start, otherwise its 1-based `row`
+    pub fn week_number(&self, row: i32, first_day: NaiveDate) -> i32 {
+        match (self.week_numbering, self.week_start) {
+            (WeekNumbering::Iso, WeekStart::Monday) => first_day.iso_week().week() as i32,
+            _ => row,
+        }
+    }
+
+    /// The color name or `#rrggbb` the date is drawn with, if any. The
+    /// first of these that applies wins:
+    ///
+    /// 1. In work mode, weekends are never colored
+    /// 2. The today color, on today
+    /// 3. A milestone on the date
+    /// 4. The color of the date's first event that has one
+    /// 5. The first range in the config that contains the date
+    /// 6. The first highlight of the date's weekday
+    pub fn color_for(&self, date: NaiveDate) -> Option<&str> {
+        self.date_color(date).map(|(color, _)| color)
+    }
+
+    /// The date's color, as [`Calendar::color_for`] picks it, and what it
+    /// comes from
+    pub(crate) fn date_color(&self, date: NaiveDate) -> Option<(&str, ColorSource)> {
+        // In work mode, never color weekends
+        if self.color_mode == ColorMode::Work && self.is_weekend(date) {
+            return None;
+        }
+
+        // Today's color wins over anything else on the date
+        if let Some(color) = self.today_color_on(date) {
+            return Some((color, ColorSource::Today));
+        }
+
+        // Milestones are highlighted above everything else
+        if let Some(milestone) = self.milestone_on(date) {
+            return Some((milestone.color(), ColorSource::Milestone));
+        }
+
+        // Check if date has a specific color
+        if let Some(detail) = self.colored_detail(date) {
+            if let Some(color) = &detail.color {
+                return Some((color, ColorSource::Detail));
+            }
+        }
+
+        // Check if date is in a range; where ranges overlap, the first
+        // one in the config wins
+        for range in &self.ranges {
+            if date >= range.start && date <= range.end {
+                return Some((&range.color, ColorSource::Range));
+            }
+        }
+
+        if let Some((_, color)) = self
+            .weekday_colors
+            .iter()
+            .find(|(day, _)| *day == date.weekday())
+        {
+            return Some((color, ColorSource::Weekday));
+        }
+
+        None
+    }
+
+    /// The header title, `COMPACT CALENDAR 2025` unless one is set
+    pub fn title(&self) -> String {
+        self.title
+            .as_deref()
+            .unwrap_or("COMPACT CALENDAR {year}")
+            .replace("{year}", &self.year.to_string())
+    }
+
+    /// The configured today color, when `date` is today
+    fn today_color_on(&self, date: NaiveDate) -> Option<&String> {
+        self.today_color.as_ref().filter(|_| date == self.today)
+    }
+
+    /// Whether `date`'s color is drawn in its dimmed shade: on past days
+    /// when the past date display dims them, and on weekends when the
+    /// weekend display dims them, unless the event or range coloring the
+    /// date sets `dim_weekends`
+    pub fn dims_color(&self, date: NaiveDate) -> bool {
+        if date < self.today && self.past_date_display.dims_color() {
+            return true;
+        }
+        if !self.is_weekend(date) {
+            return false;
+        }
+        let dim_weekends = match self.date_color(date) {
+            Some((_, ColorSource::Detail)) => self
+                .colored_detail(date)
+                .and_then(|detail| detail.dim_weekends),
+            Some((_, ColorSource::Range)) => self
+                .ranges
+                .iter()
+                .find(|range| date >= range.start && date <= range.end)
+                .and_then(|range| range.dim_weekends),
+            _ => None,
+        };
+        dim_weekends.unwrap_or(self.weekend_display == WeekendDisplay::Dimmed)
+    }
+
+    /// Whether `date` is the first or last day of the range that colors it
+    pub fn is_range_cap(&self, date: NaiveDate) -> bool {
+        let colored_by_range = matches!(self.date_color(date), Some((_, ColorSource::Range)));
+        colored_by_range
+            && self
+                .ranges
+                .iter()
+                .find(|range| date >= range.start && date <= range.end)
+                .is_some_and(|range| date == range.start || date == range.end)
+    }
+
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.weekend_days.contains(&date.weekday())
+    }
+
+    /// Count the events, ranges, and workdays within the rendered date range
+    pub fn stats(&self) -> CalendarStats {
+        let (start_date, end_date) = self.date_range();
+        let days = || start_date.iter_days().take_while(|date| *date <= end_date);
+        CalendarStats {
+            events: self
+                .details
+                .iter()
+                .filter(|(date, _)| **date >= start_date && **date <= end_date)
+                .map(|(_, events)| events.len())
+                .sum(),
+            ranges: self
+                .ranges
+                .iter()
+                .filter(|range| range.start <= end_date && range.end >= start_date)
+                .count(),
+            range_days: days()
+                .filter(|date| {
+                    self.ranges
+                        .iter()
+                        .any(|range| *date >= range.start && *date <= range.end)
+                })
+                .count(),
+            weekend_days: days().filter(|date| self.is_weekend(*date)).count(),
+            workdays: days()
+                .filter(|date| self.is_business_day(*date))
+                .filter(|date| self.color_mode != ColorMode::Work || !self.is_taken(*date))
+                .count(),
+        }
+    }
+
+    /// Whether an event, range, or milestone colors the date
+    fn is_taken(&self, date: NaiveDate) -> bool {
+        matches!(
+            self.date_color(date),
+            Some((
+                _,
+                ColorSource::Milestone | ColorSource::Detail | ColorSource::Range
+            ))
+        )
+    }
+
+    /// One line tallying [`Calendar::stats`], e.g. "2025: 251 working days,
+    /// 104 weekend days, 12 events, 3 ranges (21 days)"
+    pub fn count_summary(&self) -> String {
+        let stats = self.stats();
+        format!(
+            "{}: {}, {}, {}, {} ({})",
+            self.year,
+            pluralize(stats.workdays, "working day"),
+            pluralize(stats.weekend_days, "weekend day"),
+            pluralize(stats.events, "event"),
+            pluralize(stats.ranges, "range"),
+            pluralize(stats.range_days, "day")
+        )
+    }
+
+    /// Days of the year that have passed before today, clamped to the year
+    pub fn days_elapsed(&self) -> i64 {
+        let start = NaiveDate::from_ymd_opt(self.year, 1, 1).unwrap();
+        (self.today - start)
+            .num_days()
+            .clamp(0, self.days_in_year())
+    }
+
+    /// Days of the year from today onwards, clamped to the year
+    pub fn days_left(&self) -> i64 {
+        self.days_in_year() - self.days_elapsed()
+    }
+
+    fn days_in_year(&self) -> i64 {
+        if MonthInfo::is_leap_year(self.year) {
+            366
+        } else {
+            365
+        }
+    }
+
+    pub fn milestone_on(&self, date: NaiveDate) -> Option<&Milestone> {
+        self.milestones
+            .iter()
+            .find(|milestone| milestone.date == date)
+    }
+
+    /// Every event with its date, in no particular order of dates
+    pub fn details_iter(&self) -> impl Iterator<Item = (&NaiveDate, &DateDetail)> {
+        self.details
+            .iter()
+            .flat_map(|(date, events)| events.iter().map(move |detail| (date, detail)))
+    }
+
+    /// The date's first event with a color, which colors its cell
+    pub fn colored_detail(&self, date: NaiveDate) -> Option<&DateDetail> {
+        self.details
+            .get(&date)?
+            .iter()
+            .find(|detail| detail.color.is_some())
+    }
+
+    /// Check if a date carries an event marked as important
+    pub fn is_important(&self, date: NaiveDate) -> bool {
+        self.details
+            .get(&date)
+            .is_some_and(|events| events.iter().any(|detail| detail.important))
+            || self
+                .ranges
+                .iter()
+                .any(|range| range.important && date >= range.start && date <= range.end)
+    }
+
+    /// The effects configured for the date's events and every range over it
+    pub fn effects_on(&self, date: NaiveDate) -> Effects {
+        let detail = self
+            .details
+            .get(&date)
+            .into_iter()
+            .flatten()
+            .fold(Effects::new(), |effects, detail| effects | detail.effects);
+        self.ranges
+            .iter()
+            .filter(|range| date >= range.start && date <= range.end)
+            .fold(detail, |effects, range| effects | range.effects)
+    }
+
+    /// The events on `date` and the ranges covering it, with one lookup for
+    /// the events and one pass over the ranges
+    pub fn events_on(&self, date: NaiveDate) -> EventsOnDate<'_> {
+        EventsOnDate {
+            date,
+            details: self.details.get(&date).map_or(&[], Vec::as_slice),
+            ranges: self
+                .ranges
+                .iter()
+                .filter(|range| range.start <= date && date <= range.end)
+                .collect(),
+        }
+    }
+
+    /// Whether anything (event, range, milestone, or holiday) falls on a date
+    pub fn is_busy(&self, date: NaiveDate) -> bool {
+        self.is_holiday(date) || !self.resolve_day(date).events.is_empty()
+    }
+
+    /// Resolve the color and events of a single date
+    pub fn resolve_day(&self, date: NaiveDate) -> DayRender {
+        let mut events = Vec::new();
+        if let Some(milestone) = self.milestone_on(date) {
+            events.push(milestone.label.clone());
+        }
+        for detail in self.details.get(&date).into_iter().flatten() {
+            events.push(detail.description.clone());
+        }
+        for range in &self.ranges {
+            if date >= range.start && date <= range.end {
+                events.push(range.description.clone().unwrap_or_else(|| {
+                    format!(
+                        "{} to {}",
+                        range.start.format("%m/%d"),
+                        range.end.format("%m/%d")
+                    )
+                }));
+            }
+        }
+
+        DayRender {
+            date,
+            color: self.color_for(date).map(str::to_string),
+            events,
         }
     }
 }
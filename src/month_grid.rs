@@ -0,0 +1,143 @@
+use crate::formatting::{center, display_width, MonthInfo};
+use crate::models::Calendar;
+use crate::rendering::{CalendarRenderer, ColorChoice};
+use chrono::{Datelike, NaiveDate};
+
+/// Width of a mini month: seven two-character days separated by spaces
+const MINI_WIDTH: usize = 20;
+/// Title, weekday header, and the most weeks a month can span
+const MINI_HEIGHT: usize = 8;
+/// Space between side-by-side blocks
+const COLUMN_GAP: usize = 3;
+
+/// A small month block in the style of `cal`, with colored days.
+///
+/// Always [`MINI_HEIGHT`] lines, each [`MINI_WIDTH`] columns wide, so blocks
+/// can be laid out side by side.
+pub fn mini_month(calendar: &Calendar, month: u32) -> Vec<String> {
+    mini_month_styled(calendar, &CalendarRenderer::new(calendar), month)
+}
+
+/// [`mini_month`] with days colored by `renderer`
+pub(crate) fn mini_month_styled(
+    calendar: &Calendar,
+    renderer: &CalendarRenderer,
+    month: u32,
+) -> Vec<String> {
+    let title = format!("{} {}", calendar.locale.month_name(month), calendar.year);
+    let mut lines = vec![center(&title, MINI_WIDTH)];
+    let days: Vec<String> = calendar
+        .week_start
+        .days()
+        .iter()
+        .map(|&day| calendar.locale.weekday(day).chars().take(2).collect())
+        .collect();
+    lines.push(days.join(" "));
+
+    let first = NaiveDate::from_ymd_opt(calendar.year, month, 1).unwrap();
+    let days = MonthInfo::days_in_month(month, calendar.year);
+    let mut cells: Vec<String> = vec!["  ".to_string(); calendar.get_weekday_num(first) as usize];
+    for day in 1..=days {
+        let date = NaiveDate::from_ymd_opt(calendar.year, month, day).unwrap();
+        cells.push(renderer.styled_day(date, &format!("{:>2}", date.day())));
+    }
+    for week in cells.chunks(7) {
+        lines.push(week.join(" "));
+    }
+
+    lines.resize(MINI_HEIGHT, String::new());
+    lines
+}
+
+/// Months side by side in the year overview
+const OVERVIEW_COLUMNS: usize = 3;
+
+/// The months in the calendar's filter as rows of [`OVERVIEW_COLUMNS`]
+/// mini months under the year, like `cal -y`, with days colored by
+/// `renderer`
+pub(crate) fn overview_styled(calendar: &Calendar, renderer: &CalendarRenderer) -> Vec<String> {
+    let (start, end) = calendar
+        .month_filter
+        .get_date_range(calendar.year, calendar.today);
+    let months: Vec<u32> = (1..=12)
+        .filter(|&month| {
+            let first = NaiveDate::from_ymd_opt(calendar.year, month, 1).unwrap();
+            let last = first
+                + chrono::Duration::days(MonthInfo::days_in_month(month, calendar.year) as i64 - 1);
+            first <= end && last >= start
+        })
+        .collect();
+
+    let width = OVERVIEW_COLUMNS * MINI_WIDTH + (OVERVIEW_COLUMNS - 1) * COLUMN_GAP;
+    let mut lines = vec![center(&calendar.year.to_string(), width), String::new()];
+    for row in months.chunks(OVERVIEW_COLUMNS) {
+        let blocks: Vec<Vec<String>> = row
+            .iter()
+            .map(|&month| {
+                let mut block = mini_month_styled(calendar, renderer, month);
+                // The year is over the whole grid
+                block[0] = center(calendar.locale.month_name(month), MINI_WIDTH);
+                block
+            })
+            .collect();
+        // Rows keep room for six weeks, as `cal` does, then a blank line
+        lines.extend(zip_columns(&blocks, COLUMN_GAP));
+        lines.push(String::new());
+    }
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Lay out blocks of lines side by side, padding each column to its widest line
+pub fn zip_columns(blocks: &[Vec<String>], gap: usize) -> Vec<String> {
+    let height = blocks.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = blocks
+        .iter()
+        .map(|block| {
+            block
+                .iter()
+                .map(|line| display_width(line))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    (0..height)
+        .map(|row| {
+            let mut line = String::new();
+            for (idx, block) in blocks.iter().enumerate() {
+                let cell = block.get(row).map_or("", String::as_str);
+                line.push_str(cell);
+                if idx + 1 < blocks.len() {
+                    let pad = widths[idx] - display_width(cell) + gap;
+                    line.push_str(&" ".repeat(pad));
+                }
+            }
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Every month of each calendar, one column per year and one row per month,
+/// colored according to `color`
+pub fn render_year_grid(calendars: &[Calendar], color: ColorChoice) -> String {
+    let renderers: Vec<CalendarRenderer> = calendars
+        .iter()
+        .map(|calendar| CalendarRenderer::new(calendar).with_color_choice(color))
+        .collect();
+    let mut output = String::new();
+    for month in 1..=12 {
+        let blocks: Vec<Vec<String>> = calendars
+            .iter()
+            .zip(&renderers)
+            .map(|(calendar, renderer)| mini_month_styled(calendar, renderer, month))
+            .collect();
+        for line in zip_columns(&blocks, COLUMN_GAP) {
+            output.push_str(&line);
+            output.push('\n');
+        }
+    }
+    output
+}
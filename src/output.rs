@@ -0,0 +1,327 @@
+use crate::models::{Calendar, DayRender};
+use crate::rendering::CalendarRenderer;
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Svg,
+    DaysCsv,
+    Tsv,
+    Ics,
+    Markdown,
+    Html,
+}
+
+impl OutputFormat {
+    /// Parse an output format from its `--format` name
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "text" | "txt" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "svg" => Ok(Self::Svg),
+            "days-csv" => Ok(Self::DaysCsv),
+            "tsv" => Ok(Self::Tsv),
+            "ics" => Ok(Self::Ics),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            _ => Err(format!(
+                "Invalid format: '{}'. Use one of: text, json, svg, days-csv, tsv, ics, markdown, html",
+                name
+            )),
+        }
+    }
+
+    /// Infer an output format from a file extension, if it is a known one
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        match extension.to_lowercase().as_str() {
+            "txt" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            "svg" => Some(Self::Svg),
+            "tsv" => Some(Self::Tsv),
+            "ics" => Some(Self::Ics),
+            "md" => Some(Self::Markdown),
+            "html" | "htm" => Some(Self::Html),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonCalendar {
+    year: i32,
+    days: Vec<DayRender>,
+    weeks: Vec<JsonWeek>,
+}
+
+/// A week row as drawn, with all seven of its dates
+#[derive(Serialize)]
+struct JsonWeek {
+    week: i32,
+    dates: Vec<JsonDate>,
+}
+
+#[derive(Serialize)]
+struct JsonDate {
+    date: NaiveDate,
+    day: u32,
+    month: u32,
+    is_weekend: bool,
+    is_past: bool,
+    is_today: bool,
+    color: Option<String>,
+    description: Option<String>,
+}
+
+impl JsonDate {
+    fn new(calendar: &Calendar, date: NaiveDate) -> Self {
+        JsonDate {
+            date,
+            day: date.day(),
+            month: date.month(),
+            is_weekend: calendar.is_weekend(date),
+            is_past: date < calendar.today,
+            is_today: date == calendar.today,
+            color: calendar.color_for(date).map(str::to_string),
+            // Several events on a day are listed together, as annotated
+            description: calendar.details.get(&date).map(|events| {
+                events
+                    .iter()
+                    .map(|detail| detail.description.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }),
+        }
+    }
+}
+
+/// The week rows covering the calendar's date range
+fn json_weeks(calendar: &Calendar) -> Vec<JsonWeek> {
+    (1..)
+        .zip(calendar.weeks())
+        .map(|(row, layout)| JsonWeek {
+            week: calendar.week_number(row, layout.dates[0]),
+            dates: layout
+                .dates
+                .iter()
+                .map(|&date| JsonDate::new(calendar, date))
+                .collect(),
+        })
+        .collect()
+}
+
+pub fn render_json(calendar: &Calendar) -> String {
+    let (start_date, end_date) = calendar.date_range();
+    let days = start_date
+        .iter_days()
+        .take_while(|date| *date <= end_date)
+        .map(|date| calendar.resolve_day(date))
+        .collect();
+
+    let json = JsonCalendar {
+        year: calendar.year,
+        days,
+        weeks: json_weeks(calendar),
+    };
+    serde_json::to_string_pretty(&json).expect("calendar serializes to JSON")
+}
+
+/// One numeric row per day, for plotting tools
+pub fn render_days_csv(calendar: &Calendar) -> String {
+    let (start_date, end_date) = calendar.date_range();
+    let mut output = String::from("# ordinal,weekday_num,event_count,has_color\n");
+    for date in start_date.iter_days().take_while(|date| *date <= end_date) {
+        let day = calendar.resolve_day(date);
+        output.push_str(&format!(
+            "{},{},{},{}\n",
+            date.ordinal(),
+            calendar.get_weekday_num(date),
+            day.events.len(),
+            u8::from(day.color.is_some())
+        ));
+    }
+    output
+}
+
+/// One row per day of `date`, color and description, tab separated, with
+/// empty fields where there's nothing. Whether the day is past, today or
+/// upcoming and whether it's a weekend day follow.
+pub fn render_tsv(calendar: &Calendar) -> String {
+    let (start_date, end_date) = calendar.date_range();
+    let mut output = String::from("# date\tcolor\tdescription\twhen\tweekend\n");
+    for date in start_date.iter_days().take_while(|date| *date <= end_date) {
+        let day = calendar.resolve_day(date);
+        // Tabs and line breaks in descriptions would split the row
+        let description = day.events.join(", ").replace(['\t', '\n', '\r'], " ");
+        let when = match date.cmp(&calendar.today) {
+            std::cmp::Ordering::Less => "past",
+            std::cmp::Ordering::Equal => "today",
+            std::cmp::Ordering::Greater => "upcoming",
+        };
+        output.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            date,
+            day.color.unwrap_or_default(),
+            description,
+            when,
+            u8::from(calendar.is_weekend(date))
+        ));
+    }
+    output
+}
+
+/// Export the calendar's events and ranges as all-day iCalendar events
+pub fn render_ics(calendar: &Calendar) -> String {
+    let stamp = calendar.today.format("%Y%m%dT000000Z");
+    let mut events: Vec<(NaiveDate, NaiveDate, &str)> = calendar
+        .details_iter()
+        .map(|(date, detail)| (*date, *date, detail.description.as_str()))
+        .chain(calendar.ranges.iter().map(|range| {
+            (
+                range.start,
+                range.end,
+                range.description.as_deref().unwrap_or_default(),
+            )
+        }))
+        .collect();
+    events.sort();
+
+    let mut output = String::new();
+    let mut line = |text: String| {
+        output.push_str(&ics_fold(&text));
+        output.push_str("\r\n");
+    };
+    line("BEGIN:VCALENDAR".to_string());
+    line("VERSION:2.0".to_string());
+    line(format!(
+        "PRODID:-//compact-calendar-cli//{}//EN",
+        env!("CARGO_PKG_VERSION")
+    ));
+    for (start, end, summary) in events {
+        line("BEGIN:VEVENT".to_string());
+        // Derived from the event itself so re-exports update rather than duplicate
+        line(format!(
+            "UID:{}-{}-{:016x}@compact-calendar-cli",
+            start.format("%Y%m%d"),
+            end.format("%Y%m%d"),
+            fnv1a(summary)
+        ));
+        line(format!("DTSTAMP:{}", stamp));
+        line(format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")));
+        // All-day events end on the day after, exclusive
+        line(format!(
+            "DTEND;VALUE=DATE:{}",
+            end.succ_opt().unwrap_or(end).format("%Y%m%d")
+        ));
+        line(format!("SUMMARY:{}", ics_escape(summary)));
+        line("END:VEVENT".to_string());
+    }
+    line("END:VCALENDAR".to_string());
+    output
+}
+
+/// Escape text for an iCalendar property value
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets, continuing with a leading space
+fn ics_fold(line: &str) -> String {
+    let mut output = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            output.push_str("\r\n ");
+            width = 1;
+        }
+        output.push(c);
+        width += c.len_utf8();
+    }
+    output
+}
+
+/// 64-bit FNV-1a, a small stable hash for event UIDs
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Render `calendar` in the given format to any writer. With `colors`,
+/// text keeps its ANSI styling; the other formats are unaffected.
+pub fn render_to<W: Write>(
+    calendar: &Calendar,
+    format: OutputFormat,
+    colors: bool,
+    w: &mut W,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Text => CalendarRenderer::new(calendar)
+            .with_colors(colors)
+            .render_to_writer(w),
+        OutputFormat::Json => writeln!(w, "{}", render_json(calendar)),
+        OutputFormat::DaysCsv => w.write_all(render_days_csv(calendar).as_bytes()),
+        OutputFormat::Tsv => w.write_all(render_tsv(calendar).as_bytes()),
+        OutputFormat::Ics => w.write_all(render_ics(calendar).as_bytes()),
+        OutputFormat::Svg => {
+            w.write_all(CalendarRenderer::new(calendar).render_to_svg().as_bytes())
+        }
+        OutputFormat::Markdown => w.write_all(
+            CalendarRenderer::new(calendar)
+                .render_to_markdown()
+                .as_bytes(),
+        ),
+        OutputFormat::Html => {
+            w.write_all(CalendarRenderer::new(calendar).render_to_html().as_bytes())
+        }
+    }
+}
+
+/// Render `calendar` to the file at `path`.
+///
+/// The output is written to a temporary file next to `path` and renamed over
+/// it once complete, so a crash never leaves a truncated file behind.
+pub fn write_output(
+    calendar: &Calendar,
+    format: OutputFormat,
+    colors: bool,
+    path: &Path,
+) -> Result<(), String> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if !parent.is_dir() {
+        return Err(format!("Output directory {:?} does not exist", parent));
+    }
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("Output path {:?} is not a file", path))?;
+    let tmp_path = parent.join(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+
+    let result = File::create(&tmp_path)
+        .and_then(|mut file| {
+            render_to(calendar, format, colors, &mut file)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp_path, path));
+
+    result.map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to write output file {:?}: {}", path, e)
+    })
+}
@@ -1,12 +1,23 @@
-use crate::formatting::{MonthInfo, WeekLayout};
-use crate::models::{Calendar, ColorMode, DateDetail, PastDateDisplay, WeekStart, WeekendDisplay};
+use crate::formatting::{
+    center, display_width, jdn, pluralize, progress_bar, relative_days, truncate_display,
+    wrap_items, WeekLayout,
+};
+use crate::models::{
+    AnnotationPosition, BorderStyle, Calendar, ColorSource, DateDetail, DateRange, MonthFilter,
+    WeekendDisplay,
+};
+use crate::month_grid::{mini_month_styled, overview_styled};
 use anstyle::{AnsiColor, Color, Effects, RgbColor, Style};
-use chrono::Weekday;
-use chrono::{Datelike, NaiveDate};
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Write};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColorValue {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_fields::rgb"))]
     pub normal: RgbColor,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_fields::rgb"))]
     pub dimmed: RgbColor,
 }
 
@@ -15,6 +26,11 @@ impl ColorValue {
         Self { normal, dimmed }
     }
 
+    /// A color with its dimmed shade derived by [`dim_rgb`]
+    pub fn from_rgb(normal: RgbColor) -> Self {
+        Self::new(normal, dim_rgb(normal))
+    }
+
     pub fn get_normal_style(&self) -> Style {
         Style::new().bg_color(Some(Color::Rgb(self.normal)))
     }
@@ -22,17 +38,140 @@ impl ColorValue {
     pub fn get_dimmed_style(&self) -> Style {
         Style::new().bg_color(Some(Color::Rgb(self.dimmed)))
     }
+
+    /// A lighter shade used for the first and last day of a range
+    pub fn cap(color: RgbColor) -> RgbColor {
+        let lighten = |c: u8| c + (255 - c) / 3;
+        RgbColor(lighten(color.0), lighten(color.1), lighten(color.2))
+    }
+}
+
+impl From<RgbColor> for ColorValue {
+    fn from(normal: RgbColor) -> Self {
+        Self::from_rgb(normal)
+    }
+}
+
+/// The dimmed shade of a color: each channel scaled to 70%, rounded down.
+/// Colors without a hand-picked dimmed shade use this, and the builtin
+/// pairs are kept close to it.
+pub fn dim_rgb(color: RgbColor) -> RgbColor {
+    let dim = |c: u8| (u16::from(c) * 7 / 10) as u8;
+    RgbColor(dim(color.0), dim(color.1), dim(color.2))
+}
+
+/// When output is colored, as chosen with `--color`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Follow `FORCE_COLOR` and `NO_COLOR`, otherwise color only a terminal
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(format!(
+                "Invalid color: '{}'. Use one of: auto, always, never",
+                name
+            )),
+        }
+    }
+
+    /// Whether output written to stdout should be colored
+    pub fn colors_stdout(self) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => Self::from_env().unwrap_or_else(|| io::stdout().is_terminal()),
+        }
+    }
+
+    /// Whether output written to a file should be colored: unlike stdout,
+    /// a file keeps its colors in `Auto` unless the environment says otherwise
+    pub fn colors_file(self) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => Self::from_env().unwrap_or(true),
+        }
+    }
+
+    /// What the environment asks for: `FORCE_COLOR` (unless "0") turns
+    /// colors on, and otherwise `NO_COLOR` turns them off
+    fn from_env() -> Option<bool> {
+        match std::env::var("FORCE_COLOR") {
+            Ok(force) => Some(force != "0"),
+            Err(_) if std::env::var_os("NO_COLOR").is_some() => Some(false),
+            Err(_) => None,
+        }
+    }
+}
+
+/// How the text calendar is laid out, as chosen with `--layout`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// One row per week, annotated on the side
+    #[default]
+    Wide,
+    /// A single month as a classic `cal` block, annotations listed below
+    Mini,
+    /// The year as a grid of `cal` blocks, three across, without annotations
+    Overview,
+}
+
+impl Layout {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "wide" => Ok(Self::Wide),
+            "mini" => Ok(Self::Mini),
+            "overview" => Ok(Self::Overview),
+            _ => Err(format!(
+                "Invalid layout: '{}'. Use one of: wide, mini, overview",
+                name
+            )),
+        }
+    }
 }
 
+/// Every builtin color name, in the order `--list-colors` shows them
+pub const BUILTIN_COLORS: [&str; 15] = [
+    "orange",
+    "yellow",
+    "green",
+    "blue",
+    "purple",
+    "red",
+    "cyan",
+    "gray",
+    "light_orange",
+    "light_yellow",
+    "light_green",
+    "light_blue",
+    "light_purple",
+    "light_red",
+    "light_cyan",
+];
+
+/// Each half of a `--list-colors` swatch: normal, then dimmed
+const PALETTE_SWATCH: &str = "   ";
+
 #[derive(Debug, Clone)]
 pub struct ColorPalette {
     colors_enabled: bool,
+    custom: HashMap<String, ColorValue>,
 }
 
 impl Default for ColorPalette {
     fn default() -> Self {
         Self {
-            colors_enabled: !Self::is_color_disabled(),
+            colors_enabled: ColorChoice::Auto.colors_stdout(),
+            custom: HashMap::new(),
         }
     }
 }
@@ -42,15 +181,36 @@ impl ColorPalette {
         Self::default()
     }
 
-    fn is_color_disabled() -> bool {
-        std::env::var("NO_COLOR").is_ok()
+    /// Add user-defined colors, which win over builtins of the same name
+    pub fn with_custom_colors(mut self, colors: &HashMap<String, ColorValue>) -> Self {
+        self.custom
+            .extend(colors.iter().map(|(name, value)| (name.clone(), *value)));
+        self
+    }
+
+    /// Look up `name` among the user colors, then the builtins
+    pub fn color_value(&self, name: &str) -> Option<ColorValue> {
+        self.custom
+            .get(name)
+            .copied()
+            .or_else(|| Self::get_color_value(name))
+    }
+
+    /// Decide whether to color by `choice` instead of [`ColorChoice::Auto`]
+    pub fn with_color_choice(mut self, choice: ColorChoice) -> Self {
+        self.colors_enabled = choice.colors_stdout();
+        self
     }
 
     pub fn are_colors_enabled(&self) -> bool {
         self.colors_enabled
     }
 
+    /// A builtin color by name, or an inline `#rrggbb` color
     pub fn get_color_value(name: &str) -> Option<ColorValue> {
+        if name.starts_with('#') {
+            return parse_hex_color(name).ok().map(ColorValue::from_rgb);
+        }
         match name {
             "orange" => Some(ColorValue::new(
                 RgbColor(255, 143, 64),
@@ -118,7 +278,7 @@ impl ColorPalette {
             return Style::new();
         }
 
-        if let Some(color_value) = Self::get_color_value(color_name) {
+        if let Some(color_value) = self.color_value(color_name) {
             if dimmed {
                 color_value.get_dimmed_style()
             } else {
@@ -132,29 +292,51 @@ impl ColorPalette {
     pub fn black_text() -> Style {
         Style::new().fg_color(Some(Color::Ansi(AnsiColor::Black)))
     }
+
+    /// One line per builtin color: a swatch of its normal and dimmed shades,
+    /// then its name. Without colors, only the names are listed.
+    pub fn list_colors_to_string(&self) -> String {
+        let mut output = String::new();
+        for name in BUILTIN_COLORS {
+            if self.colors_enabled {
+                for dimmed in [false, true] {
+                    let style = self.get_style(name, dimmed);
+                    output.push_str(&format!(
+                        "{}{}{}",
+                        style.render(),
+                        PALETTE_SWATCH,
+                        style.render_reset()
+                    ));
+                }
+                output.push(' ');
+            }
+            output.push_str(name);
+            output.push('\n');
+        }
+        output
+    }
 }
 
 struct ColorCodes;
 
 impl ColorCodes {
-    fn is_color_disabled() -> bool {
-        std::env::var("NO_COLOR").is_ok()
+    fn get_bg_color(palette: &ColorPalette, color: &str) -> Style {
+        palette
+            .color_value(color)
+            .map_or_else(Style::new, |c| c.get_normal_style())
     }
 
-    fn get_bg_color(color: &str) -> Style {
-        if Self::is_color_disabled() {
-            return Style::new();
-        }
-        let palette = ColorPalette::new();
-        palette.get_style(color, false)
+    fn get_dimmed_bg_color(palette: &ColorPalette, color: &str) -> Style {
+        palette
+            .color_value(color)
+            .map_or_else(Style::new, |c| c.get_dimmed_style())
     }
 
-    fn get_dimmed_bg_color(color: &str) -> Style {
-        if Self::is_color_disabled() {
-            return Style::new();
-        }
-        let palette = ColorPalette::new();
-        palette.get_style(color, true)
+    fn get_cap_bg_color(palette: &ColorPalette, color: &str, dimmed: bool) -> Style {
+        palette.color_value(color).map_or_else(Style::new, |c| {
+            let base = if dimmed { c.dimmed } else { c.normal };
+            Style::new().bg_color(Some(Color::Rgb(ColorValue::cap(base))))
+        })
     }
 
     fn black_text() -> Style {
@@ -172,743 +354,1855 @@ impl ColorCodes {
     fn dim() -> Effects {
         Effects::DIMMED
     }
+
+    fn bold() -> Effects {
+        Effects::BOLD
+    }
 }
 
 const DAYS_IN_WEEK: usize = 7;
+/// A day's flag, two digits, and trailing marker plus the gap to the next day
+const CELL_WIDTH: usize = 5;
+/// Markers for the days of a week with `--footnotes`, superscript in the
+/// one character after the day number
+const FOOTNOTE_MARKERS: [char; DAYS_IN_WEEK] = ['¹', '²', '³', '⁴', '⁵', '⁶', '⁷'];
+const ASCII_FOOTNOTE_MARKERS: [char; DAYS_IN_WEEK] = ['a', 'b', 'c', 'd', 'e', 'f', 'g'];
 const CALENDAR_WIDTH: usize = 34;
-const HEADER_WIDTH: usize = 48;
+/// Space between the week label and the month name, plus the longest month name
+const MONTH_LABEL_WIDTH: usize = 10;
+const PROGRESS_BAR_WIDTH: usize = 20;
+/// The passed and remaining parts of the year progress bar
+const PROGRESS_GLYPHS: (char, char) = ('█', '░');
+const ASCII_PROGRESS_GLYPHS: (char, char) = ('#', '-');
+/// Flag before a milestone's day number
+const MILESTONE_FLAG: char = '▶';
+const ASCII_MILESTONE_FLAG: char = '>';
+/// Free/busy segments for a day with and without events
+const FREEBUSY_BUSY: char = '█';
+const FREEBUSY_FREE: char = '░';
+const ASCII_FREEBUSY_BUSY: char = '#';
+const ASCII_FREEBUSY_FREE: char = '.';
+/// The heat cell and the space after it
+const HEAT_WIDTH: usize = 2;
+/// Density characters for 0, 1, 2, 3, and 4+ events in a week
+const HEAT_LEVELS: [char; 5] = [' ', '░', '▒', '▓', '█'];
+const ASCII_HEAT_LEVELS: [char; 5] = [' ', '.', ':', '+', '#'];
+/// Styles for the HTML table; dimmed weekends fade like in the terminal
+const HTML_STYLE: &str = "<style>\n\
+.compact-calendar { border-collapse: collapse; font-family: monospace; }\n\
+.compact-calendar th, .compact-calendar td { padding: 2px 6px; text-align: center; }\n\
+.compact-calendar td.events { text-align: left; }\n\
+.compact-calendar td.dimmed { opacity: 0.6; }\n\
+</style>\n";
+/// Colored block shown beside each color in the legend
+const LEGEND_SWATCH: &str = "██";
+const ASCII_LEGEND_SWATCH: &str = "##";
+/// Weekend text on light terminal backgrounds
+const LIGHT_BG_WEEKEND_TEXT: RgbColor = RgbColor(110, 110, 110);
+
+/// SVG geometry, in pixels
+const SVG_CELL: usize = 28;
+const SVG_LEFT: usize = 110;
+const SVG_TOP: usize = 56;
+const SVG_ANNOTATION_GAP: usize = 16;
+const SVG_ANNOTATION_WIDTH: usize = 480;
 
 pub struct CalendarRenderer<'a> {
     calendar: &'a Calendar,
+    palette: ColorPalette,
+    colors_enabled: bool,
 }
 
 impl<'a> CalendarRenderer<'a> {
+    /// A renderer that colors its output as [`ColorChoice::Auto`] decides
     pub fn new(calendar: &'a Calendar) -> Self {
-        CalendarRenderer { calendar }
+        let palette = ColorPalette::new().with_custom_colors(&calendar.colors);
+        CalendarRenderer {
+            calendar,
+            colors_enabled: palette.are_colors_enabled(),
+            palette,
+        }
+    }
+
+    /// Turn colors on or off regardless of the environment
+    pub fn with_colors(mut self, enabled: bool) -> Self {
+        self.colors_enabled = enabled;
+        self
+    }
+
+    /// Decide whether to color by `choice`, e.g. from `--color`
+    pub fn with_color_choice(mut self, choice: ColorChoice) -> Self {
+        self.palette = self.palette.with_color_choice(choice);
+        self.colors_enabled = self.palette.are_colors_enabled();
+        self
     }
 
     pub fn render(&self) {
-        self.print_header();
-        self.print_weeks();
-        println!();
+        let stdout = io::stdout();
+        if let Err(e) = self.render_to_writer(&mut stdout.lock()) {
+            eprintln!("Failed to write calendar: {}", e);
+            std::process::exit(1);
+        }
     }
 
-    pub fn render_to_string(&self) -> String {
-        let mut output = String::new();
+    /// Write the free/busy view, one bar per week, to `w`
+    pub fn render_freebusy_to_writer<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(self.freebusy_to_string().as_bytes())
+    }
 
-        let prev_no_color = std::env::var("NO_COLOR").ok();
-        std::env::set_var("NO_COLOR", "1");
+    /// The free/busy view without colors
+    pub fn render_freebusy_to_string(&self) -> String {
+        self.plain().freebusy_to_string()
+    }
 
-        output.push_str(&self.header_to_string());
-        output.push_str(&self.weeks_to_string());
-        output.push('\n');
+    /// Write `month` in the mini layout to `w`
+    pub fn render_mini_to_writer<W: Write>(&self, month: u32, w: &mut W) -> io::Result<()> {
+        w.write_all(self.mini_to_string(month).as_bytes())
+    }
+
+    /// `month` in the mini layout without colors
+    pub fn render_mini_to_string(&self, month: u32) -> String {
+        self.plain().mini_to_string(month)
+    }
+
+    /// Write the year in the overview layout to `w`
+    pub fn render_overview_to_writer<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(self.overview_to_string().as_bytes())
+    }
+
+    /// The overview layout without colors
+    pub fn render_overview_to_string(&self) -> String {
+        self.plain().overview_to_string()
+    }
 
-        match prev_no_color {
-            Some(val) => std::env::set_var("NO_COLOR", val),
-            None => std::env::remove_var("NO_COLOR"),
+    /// Write the rendered calendar, including any colors, to `w`
+    pub fn render_to_writer<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let mut body = self.header_to_string();
+        body.push_str(&self.weeks_to_string());
+        body.push_str(&self.pinned_to_string());
+        if self.calendar.legend {
+            body.push_str(&self.legend_to_string());
+        }
+        if self.calendar.year_progress {
+            body.push_str(&self.year_progress_to_string());
         }
 
-        output
+        if self.calendar.framed {
+            w.write_all(self.frame(&body).as_bytes())?;
+        } else {
+            w.write_all(body.as_bytes())?;
+        }
+        writeln!(w)
     }
 
-    /// Check if a week should be rendered based on month filter
-    fn should_render_week(&self, layout: &WeekLayout) -> bool {
-        // Include week if ANY of its 7 days fall within the filtered month range
-        layout.dates.iter().any(|date| {
-            if date.year() != self.calendar.year {
-                false
-            } else {
-                self.calendar
-                    .month_filter
-                    .should_display_month(date.month(), self.calendar.year)
+    /// Pinned events the month filter hid from the grid, listed by date
+    fn pinned_to_string(&self) -> String {
+        let (start, end) = self.get_filtered_date_range(&self.calendar.month_filter);
+        let mut pinned: Vec<(NaiveDate, String)> = Vec::new();
+
+        for (date, detail) in self.calendar.details_iter() {
+            if detail.pinned && (*date < start || *date > end) {
+                let text = self.detail_text(*date, detail);
+                pinned.push((
+                    *date,
+                    self.styled_annotation(
+                        detail.color.as_deref(),
+                        detail.important,
+                        detail.effects,
+                        &text,
+                    ),
+                ));
             }
-        })
-    }
+        }
+        for range in &self.calendar.ranges {
+            if range.pinned && (range.end < start || range.start > end) {
+                let mut text = self.range_dates(range);
+                if let Some(desc) = &range.description {
+                    text.push_str(&format!(" - {}", desc));
+                }
+                pinned.push((
+                    range.start,
+                    self.styled_annotation(
+                        Some(&range.color),
+                        range.important,
+                        range.effects,
+                        &text,
+                    ),
+                ));
+            }
+        }
 
-    /// Get the filtered date range based on month filter
-    fn get_filtered_date_range(&self) -> (NaiveDate, NaiveDate) {
-        self.calendar
-            .month_filter
-            .get_date_range(self.calendar.year)
+        if pinned.is_empty() {
+            return String::new();
+        }
+        pinned.sort_by_key(|(date, _)| *date);
+        let mut output = String::from("Pinned:\n");
+        for (_, text) in pinned {
+            output.push_str(&format!("  {}\n", text));
+        }
+        output
     }
 
-    fn header_to_string(&self) -> String {
-        let mut output = String::new();
-        output.push_str(&format!("┌{:─<width$}┐\n", "", width = HEADER_WIDTH));
+    /// Each color used by the shown events, with its `[legend]` label
+    fn legend_to_string(&self) -> String {
+        let (start, end) = self.get_filtered_date_range(&self.calendar.month_filter);
+        let mut used: Vec<(NaiveDate, &str)> = Vec::new();
+        for (date, detail) in self.calendar.details_iter() {
+            if let Some(color) = &detail.color {
+                if *date >= start && *date <= end {
+                    used.push((*date, color));
+                }
+            }
+        }
+        for range in &self.calendar.ranges {
+            if range.start <= end && range.end >= start {
+                used.push((range.start.max(start), &range.color));
+            }
+        }
 
-        // Center the title
-        let title = format!("COMPACT CALENDAR {}", self.calendar.year);
-        output.push_str(&format!("│{:^width$}│\n", title, width = HEADER_WIDTH));
+        if used.is_empty() {
+            return String::new();
+        }
+        // List colors in the order they first appear
+        used.sort();
+        let mut colors: Vec<&str> = Vec::new();
+        for (_, color) in used {
+            if !colors.contains(&color) {
+                colors.push(color);
+            }
+        }
 
-        output.push_str(&format!("├{:─<width$}┤\n", "", width = HEADER_WIDTH));
-        output.push_str("│              ");
-        match self.calendar.week_start {
-            WeekStart::Monday => output.push_str("Mon  Tue  Wed  Thu  Fri  Sat  Sun │\n"),
-            WeekStart::Sunday => output.push_str("Sun  Mon  Tue  Wed  Thu  Fri  Sat │\n"),
+        let swatch = if self.ascii() {
+            ASCII_LEGEND_SWATCH
+        } else {
+            LEGEND_SWATCH
+        };
+        let mut output = String::from("Legend:\n");
+        for color in colors {
+            let swatch = match self.palette.color_value(color) {
+                Some(value) if self.colors_enabled => {
+                    let style = Style::new().fg_color(Some(Color::Rgb(value.normal)));
+                    format!("{}{}{}", style.render(), swatch, style.render_reset())
+                }
+                _ => swatch.to_string(),
+            };
+            match self.calendar.legend_labels.get(color) {
+                Some(label) => output.push_str(&format!("  {} {} - {}\n", swatch, color, label)),
+                None => output.push_str(&format!("  {} {}\n", swatch, color)),
+            }
         }
         output
     }
 
-    fn weeks_to_string(&self) -> String {
-        let mut output = String::new();
-        let (start_date, end_date) = self.get_filtered_date_range();
+    /// A bar showing how much of the year has passed, e.g. `[████░░] 62% of 2024`
+    fn year_progress_to_string(&self) -> String {
+        let fraction = self.calendar.days_elapsed() as f64
+            / (self.calendar.days_elapsed() + self.calendar.days_left()) as f64;
+        let glyphs = if self.ascii() {
+            ASCII_PROGRESS_GLYPHS
+        } else {
+            PROGRESS_GLYPHS
+        };
+        let (filled, empty) = progress_bar(fraction, PROGRESS_BAR_WIDTH, glyphs);
+        let filled = if !self.colors_enabled {
+            filled
+        } else {
+            let style = self
+                .palette
+                .color_value("green")
+                .map_or_else(Style::new, |c| {
+                    Style::new().fg_color(Some(Color::Rgb(c.normal)))
+                });
+            format!("{}{}{}", style.render(), filled, style.render_reset())
+        };
+        format!(
+            "[{}{}] {:.0}% of {}\n",
+            filled,
+            empty,
+            fraction * 100.0,
+            self.calendar.year
+        )
+    }
 
-        let mut current_date = self.align_to_week_start(start_date);
-        let mut week_num = 1;
-        let mut current_month: Option<u32> = None;
+    /// Wrap `body` in an outer border with the title on top and stats below
+    fn frame(&self, body: &str) -> String {
+        let b = self.border_chars();
+        let f = b.frame;
+        let stats = self.calendar.stats();
+        let footer = format!(
+            "{} {sep} {} {sep} {}",
+            pluralize(stats.events, "event"),
+            pluralize(stats.ranges, "range"),
+            pluralize(stats.workdays, "workday"),
+            sep = f.separator
+        );
+        let title = format!(" {} ", self.calendar.title());
+
+        let lines: Vec<&str> = body.lines().collect();
+        let width = lines
+            .iter()
+            .map(|line| display_width(line))
+            .chain([display_width(&footer), display_width(&title)])
+            .max()
+            .unwrap_or(0);
+
+        let framed_line = |line: &str| {
+            format!(
+                "{} {}{: <pad$} {}\n",
+                f.vertical,
+                line,
+                "",
+                f.vertical,
+                pad = width - display_width(line)
+            )
+        };
+        let mut output = format!(
+            "{}{}{}{}{}\n",
+            f.top_left,
+            f.horizontal,
+            title,
+            rule(f.horizontal, width - display_width(&title) + 1),
+            f.top_right
+        );
+        for line in lines {
+            output.push_str(&framed_line(line));
+        }
+        output.push_str(&format!(
+            "{}{}{}\n",
+            f.divider_left,
+            rule(b.horizontal, width + 2),
+            f.divider_right
+        ));
+        output.push_str(&framed_line(&footer));
+        output.push_str(&format!(
+            "{}{}{}\n",
+            f.bottom_left,
+            rule(f.horizontal, width + 2),
+            f.bottom_right
+        ));
+        output
+    }
 
-        let mut details_queue: Vec<(NaiveDate, DateDetail)> = Vec::new();
-        let mut shown_ranges: Vec<usize> = Vec::new();
+    pub fn render_to_string(&self) -> String {
+        let renderer = self.plain();
+        let mut output = Vec::new();
+        renderer
+            .render_to_writer(&mut output)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(output).expect("rendered calendar is valid UTF-8")
+    }
 
-        let mut is_first_month = true;
+    /// Like [`render_to_string`](Self::render_to_string) but colored, ANSI
+    /// escapes and all, whatever `NO_COLOR` or `--color` say
+    pub fn render_colored_to_string(&self) -> String {
+        let renderer = CalendarRenderer {
+            colors_enabled: true,
+            ..self.plain()
+        };
+        let mut output = Vec::new();
+        renderer
+            .render_to_writer(&mut output)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(output).expect("rendered calendar is valid UTF-8")
+    }
 
-        while current_date <= end_date {
-            let layout = WeekLayout::new(current_date);
+    /// One line per week with a segment per day, filled when the day is busy
+    fn freebusy_to_string(&self) -> String {
+        let label_width = self.calendar.week_label_format.width();
+        let days: String = self
+            .calendar
+            .week_start
+            .days()
+            .iter()
+            .filter_map(|&day| self.calendar.locale.weekday(day).chars().next())
+            .collect();
+        let mut output = format!("{:width$} {}\n", "", days, width = label_width + 6);
+
+        for (week_num, layout) in (1..).zip(self.calendar.weeks()) {
+            let week_start = layout.dates[0];
+            let bar: String = layout
+                .dates
+                .iter()
+                .map(|&date| self.freebusy_segment(date))
+                .collect();
+            output.push_str(&format!(
+                "{:<width$} {} {}\n",
+                self.week_label(week_num, week_start),
+                week_start.format("%m/%d"),
+                bar,
+                width = label_width
+            ));
+        }
+        output
+    }
 
-            // Skip weeks that don't contain filtered months
-            if !self.should_render_week(&layout) {
-                current_date = current_date
-                    .checked_add_signed(chrono::Duration::days(DAYS_IN_WEEK as i64))
-                    .unwrap();
-                continue;
-            }
+    fn freebusy_segment(&self, date: NaiveDate) -> String {
+        let busy = self.calendar.is_busy(date);
+        let segment = match (busy, self.ascii()) {
+            (true, false) => FREEBUSY_BUSY,
+            (false, false) => FREEBUSY_FREE,
+            (true, true) => ASCII_FREEBUSY_BUSY,
+            (false, true) => ASCII_FREEBUSY_FREE,
+        };
+        if !self.colors_enabled {
+            return segment.to_string();
+        }
 
-            let next_week_date = current_date
-                .checked_add_signed(chrono::Duration::days(DAYS_IN_WEEK as i64))
-                .unwrap();
-            let next_layout = WeekLayout::new(next_week_date);
+        let style = if busy {
+            self.calendar
+                .color_for(date)
+                .and_then(|color| self.palette.color_value(color))
+                .map_or_else(Style::new, |c| {
+                    Style::new().fg_color(Some(Color::Rgb(c.normal)))
+                })
+        } else {
+            Style::new().effects(Effects::DIMMED)
+        };
+        format!("{}{}{}", style.render(), segment, style.render_reset())
+    }
 
-            if let Some((_, month)) = layout.month_start_idx {
-                current_month = Some(month);
-                if is_first_month {
-                    output.push_str(&self.month_border_to_string(&layout, current_month));
-                    is_first_month = false;
-                }
-            }
+    /// The month's block, then each of its annotations on a line of its own
+    fn mini_to_string(&self, month: u32) -> String {
+        let mut lines = mini_month_styled(self.calendar, self, month);
+        while lines.last().is_some_and(String::is_empty) {
+            lines.pop();
+        }
 
+        let mut details_queue: Vec<(NaiveDate, DateDetail)> = Vec::new();
+        let mut shown_ranges: Vec<usize> = Vec::new();
+        let mut annotations = Vec::new();
+        for layout in self.calendar.weeks_for(&MonthFilter::Single(month)) {
             self.collect_details(&layout, &mut details_queue);
-
-            output.push_str(&self.week_row_to_string(week_num, &layout, current_month));
-
-            output.push_str(&self.annotations_to_string(
+            annotations.extend(self.week_annotations(
                 &layout,
                 &mut details_queue,
                 &mut shown_ranges,
             ));
+        }
+        if !annotations.is_empty() {
+            lines.push(String::new());
+            lines.extend(annotations);
+        }
 
+        let mut output = String::new();
+        for line in lines {
+            output.push_str(line.trim_end());
             output.push('\n');
-
-            let is_last_week =
-                next_week_date.year() > self.calendar.year || next_week_date > end_date;
-
-            if is_last_week {
-                let mut month_boundary_idx = None;
-                for (idx, &date) in layout.dates.iter().enumerate() {
-                    if idx > 0 {
-                        let prev_date = layout.dates[idx - 1];
-                        if date.month() != prev_date.month() || date.year() != prev_date.year() {
-                            month_boundary_idx = Some(idx);
-                            break;
-                        }
-                    }
-                }
-
-                if let Some(boundary_idx) = month_boundary_idx {
-                    let dashes_before = (boundary_idx - 1) * 5 + 4;
-                    let dashes_after = (DAYS_IN_WEEK - boundary_idx) * 5 - 1;
-                    output.push_str(&format!(
-                        "└{:─<13}┴{:─<before$}┴{:─<after$}┘\n",
-                        "",
-                        "",
-                        "",
-                        before = dashes_before,
-                        after = dashes_after
-                    ));
-                } else {
-                    output.push_str(&format!(
-                        "└{:─<13}┴{:─<width$}┘\n",
-                        "",
-                        "",
-                        width = CALENDAR_WIDTH
-                    ));
-                }
-            } else if let Some((idx, _)) = layout.month_start_idx {
-                if idx > 0 {
-                    output.push_str(&self.separator_to_string(&layout, current_month));
-                }
-            } else if next_layout.month_start_idx.is_some()
-                && next_week_date <= end_date
-                && next_week_date.year() == self.calendar.year
-            {
-                output.push_str(&self.separator_before_month_to_string(
-                    &layout,
-                    current_month,
-                    &next_layout,
-                ));
-            }
-
-            current_date = next_week_date;
-            week_num += 1;
-
-            if current_date.year() > self.calendar.year {
-                break;
-            }
         }
-
+        output.push('\n');
         output
     }
 
-    fn month_border_to_string(&self, layout: &WeekLayout, _current_month: Option<u32>) -> String {
+    fn overview_to_string(&self) -> String {
         let mut output = String::new();
-        if let Some((idx, _)) = layout.month_start_idx {
-            if idx > 0 {
-                output.push_str("│             ┌");
-                let dashes_before = (idx - 1) * 5 + 4;
-                for _ in 0..dashes_before {
-                    output.push('─');
-                }
-                output.push('┬');
-                let dashes_after = (DAYS_IN_WEEK - idx) * 5 - 1;
-                output.push_str(&format!("{:─<width$}┤\n", "", width = dashes_after));
-            }
+        for line in overview_styled(self.calendar, self) {
+            output.push_str(line.trim_end());
+            output.push('\n');
         }
+        output.push('\n');
         output
     }
 
-    fn week_row_to_string(
-        &self,
-        week_num: i32,
-        layout: &WeekLayout,
-        _current_month: Option<u32>,
-    ) -> String {
-        let mut output = String::new();
-        let month_name = if let Some((_, month)) = layout.month_start_idx {
-            MonthInfo::from_month(month).name
-        } else {
-            ""
-        };
+    /// Describe the grid geometry and each rendered week's boundaries, for
+    /// debugging layout issues
+    pub fn explain_layout(&self) -> String {
+        let left = self.left_width();
+        let first_day = 1 + left + 2;
+        let offsets: Vec<String> = (0..DAYS_IN_WEEK)
+            .map(|idx| (first_day + idx * CELL_WIDTH).to_string())
+            .collect();
 
-        if !month_name.is_empty() {
-            output.push_str(&format!("│W{:02} {:<9}", week_num, month_name));
-        } else {
-            output.push_str(&format!("│W{:02}          ", week_num));
+        let mut output = String::new();
+        output.push_str(&format!("cell_width: {}\n", CELL_WIDTH));
+        output.push_str(&format!("left_width: {}\n", left));
+        output.push_str(&format!("calendar_width: {}\n", CALENDAR_WIDTH));
+        output.push_str(&format!("total_width: {}\n", self.header_width() + 2));
+        output.push_str(&format!("day_offsets: {}\n", offsets.join(",")));
+
+        for (week_num, layout) in (1..).zip(self.calendar.weeks()) {
+            let week_start = layout.dates[0];
+            let boundaries: Vec<String> = (1..DAYS_IN_WEEK)
+                .filter(|&idx| layout.dates[idx].month() != layout.dates[idx - 1].month())
+                .map(|idx| idx.to_string())
+                .collect();
+            output.push_str(&format!(
+                "{} {}: month_start_idx={:?} month_end_idx={:?} year_boundary_idx={:?} boundaries=[{}]\n",
+                self.week_label(week_num, week_start),
+                week_start,
+                layout.month_start_idx,
+                layout.month_end_idx,
+                layout.year_boundary_idx,
+                boundaries.join(",")
+            ));
         }
+        output
+    }
 
-        output.push('│');
-
-        for (idx, &date) in layout.dates.iter().enumerate() {
-            let is_month_boundary = if idx > 0 {
-                let prev_date = layout.dates[idx - 1];
-                date.month() != prev_date.month() || date.year() != prev_date.year()
-            } else {
-                false
-            };
+    /// Render a single month's block, with the header, as plain text
+    pub fn render_month_to_string(&self, month: u32) -> String {
+        let renderer = self.plain();
+        let mut output = renderer.header_to_string();
+        output.push_str(&renderer.filtered_weeks_to_string(&MonthFilter::Single(month)));
+        output.push('\n');
+        output
+    }
 
-            if is_month_boundary {
-                output.push('│');
-            }
+    /// Render the calendar as a standalone SVG image
+    pub fn render_to_svg(&self) -> String {
+        if self.colors_enabled {
+            return self.plain().render_to_svg();
+        }
+        let (start_date, end_date) = self.calendar.date_range();
+        let first_week = self.calendar.week_start_of(start_date);
+        let rows = self.calendar.weeks().count();
+        let grid_right = SVG_LEFT + DAYS_IN_WEEK * SVG_CELL;
+        let width = grid_right + SVG_ANNOTATION_GAP + SVG_ANNOTATION_WIDTH;
+        let height = SVG_TOP + rows * SVG_CELL + SVG_CELL / 2;
 
-            output.push_str(&format!(" {:02}", date.day()));
+        let mut output = String::new();
+        output.push_str(&format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\" font-family=\"monospace\" font-size=\"12\">\n",
+            w = width,
+            h = height
+        ));
+        output.push_str(&format!(
+            "<text x=\"{}\" y=\"24\" text-anchor=\"middle\" font-weight=\"bold\">{}</text>\n",
+            (SVG_LEFT + grid_right) / 2,
+            xml_escape(&self.calendar.title())
+        ));
+        for (col, &day) in self.calendar.week_start.days().iter().enumerate() {
+            output.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+                SVG_LEFT + col * SVG_CELL + SVG_CELL / 2,
+                SVG_TOP - 8,
+                xml_escape(self.calendar.locale.weekday(day))
+            ));
+        }
 
-            if idx < 6 {
-                let next_date = layout.dates[idx + 1];
-                let next_is_boundary =
-                    date.month() != next_date.month() || date.year() != next_date.year();
-                if next_is_boundary {
-                    output.push(' ');
-                } else {
-                    output.push_str("  ");
-                }
-            } else {
-                output.push(' ');
+        output.push_str(&self.svg_weeks(grid_right));
+
+        // iter_days stops at NaiveDate::MAX rather than overflowing
+        let mut days = start_date
+            .iter_days()
+            .take_while(|date| *date <= end_date)
+            .peekable();
+        while let Some(&first) = days.peek() {
+            let month = first.month();
+            output.push_str(&format!(
+                "<g class=\"month\" id=\"month-{}\">\n",
+                first.format("%Y-%m")
+            ));
+            output.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n",
+                SVG_LEFT - 6,
+                self.svg_row_y(first_week, first) + SVG_CELL / 2 + 4,
+                self.calendar.locale.month_name(month)
+            ));
+            while let Some(date) = days.next_if(|date| date.month() == month) {
+                output.push_str(&self.svg_day(first_week, date));
             }
+            output.push_str("</g>\n");
         }
 
-        output.push('│');
+        output.push_str("</svg>\n");
         output
     }
 
-    fn annotations_to_string(
-        &self,
-        layout: &WeekLayout,
-        details_queue: &mut Vec<(NaiveDate, DateDetail)>,
-        shown_ranges: &mut Vec<usize>,
-    ) -> String {
-        let mut output = String::new();
-        let week_start = layout.dates[0];
-        let week_end = layout.dates[DAYS_IN_WEEK - 1];
-        let mut annotations = Vec::new();
+    /// Render the calendar as a Markdown table, one row per week. Days with
+    /// events are bold, since Markdown has no background colors.
+    pub fn render_to_markdown(&self) -> String {
+        if self.colors_enabled {
+            return self.plain().render_to_markdown();
+        }
+        let days: Vec<String> = self
+            .calendar
+            .week_start
+            .days()
+            .iter()
+            .map(|&day| self.calendar.locale.weekday(day).to_string())
+            .collect();
+        let mut output = format!("## {}\n\n", markdown_escape(&self.calendar.title()));
+        output.push_str(&format!("| Week | {} | Events |\n", days.join(" | ")));
+        output.push_str(&format!("|---|{}---|\n", "---|".repeat(DAYS_IN_WEEK)));
+
+        let (start_date, _) = self.calendar.date_range();
+        let mut current_month = None;
+        let mut details_queue: Vec<(NaiveDate, DateDetail)> = Vec::new();
+        let mut shown_ranges: Vec<usize> = Vec::new();
 
-        // Collect all details that occur in this week
-        let mut details_to_remove = Vec::new();
-        for (i, (detail_date, detail)) in details_queue.iter().enumerate() {
-            if *detail_date >= week_start && *detail_date <= week_end {
-                annotations.push(format!(
-                    "{} - {}",
-                    detail_date.format("%m/%d"),
-                    detail.description
-                ));
-                details_to_remove.push(i);
+        for (week_num, layout) in (1..).zip(self.calendar.weeks()) {
+            let mut label = self.week_label(week_num, layout.dates[0]);
+            let month =
+                self.update_current_month(&layout, start_date, &mut current_month, week_num == 1);
+            if let Some(month) = month {
+                label.push(' ');
+                label.push_str(self.calendar.locale.month_name(month));
             }
+            let cells: Vec<String> = layout
+                .dates
+                .iter()
+                .map(|&date| {
+                    if self.calendar.color_for(date).is_some() {
+                        format!("**{:02}**", date.day())
+                    } else {
+                        format!("{:02}", date.day())
+                    }
+                })
+                .collect();
+
+            self.collect_details(&layout, &mut details_queue);
+            let annotations = self.week_annotations(&layout, &mut details_queue, &mut shown_ranges);
+            output.push_str(&format!(
+                "| {} | {} | {} |\n",
+                label,
+                cells.join(" | "),
+                markdown_escape(&annotations.join(", "))
+            ));
+        }
+        output
+    }
+
+    /// Render the calendar as an HTML table, one row per week, with each
+    /// day's terminal colors and effects as inline styles
+    pub fn render_to_html(&self) -> String {
+        if self.colors_enabled {
+            return self.plain().render_to_html();
         }
-        // Remove details in reverse order to maintain indices
-        for &i in details_to_remove.iter().rev() {
-            details_queue.remove(i);
+        let mut output = String::from(HTML_STYLE);
+        output.push_str("<table class=\"compact-calendar\">\n");
+        output.push_str(&format!(
+            "<caption>{}</caption>\n",
+            xml_escape(&self.calendar.title())
+        ));
+        output.push_str("<thead><tr><th>Week</th>");
+        for day in self.calendar.week_start.days() {
+            output.push_str(&format!("<th>{}</th>", self.calendar.locale.weekday(day)));
         }
+        output.push_str("<th>Events</th></tr></thead>\n<tbody>\n");
 
-        // Collect all ranges that overlap with this week
-        for (idx, range) in self.calendar.ranges.iter().enumerate() {
-            if !shown_ranges.contains(&idx) && range.start <= week_end && range.end >= week_start {
-                if let Some(desc) = &range.description {
-                    annotations.push(format!(
-                        "{} to {} - {}",
-                        range.start.format("%m/%d"),
-                        range.end.format("%m/%d"),
-                        desc
-                    ));
-                } else {
-                    annotations.push(format!(
-                        "{} to {}",
-                        range.start.format("%m/%d"),
-                        range.end.format("%m/%d")
-                    ));
-                }
-                shown_ranges.push(idx);
+        let (start_date, _) = self.calendar.date_range();
+        let mut current_month = None;
+        let mut details_queue: Vec<(NaiveDate, DateDetail)> = Vec::new();
+        let mut shown_ranges: Vec<usize> = Vec::new();
+
+        for (week_num, layout) in (1..).zip(self.calendar.weeks()) {
+            let mut label = self.week_label(week_num, layout.dates[0]);
+            let month =
+                self.update_current_month(&layout, start_date, &mut current_month, week_num == 1);
+            if let Some(month) = month {
+                label.push(' ');
+                label.push_str(self.calendar.locale.month_name(month));
+            }
+            output.push_str(&format!("<tr><th>{}</th>", xml_escape(&label)));
+            for &date in &layout.dates {
+                output.push_str(&self.html_day(date));
             }
+
+            self.collect_details(&layout, &mut details_queue);
+            let annotations = self.week_annotations(&layout, &mut details_queue, &mut shown_ranges);
+            output.push_str(&format!(
+                "<td class=\"events\">{}</td></tr>\n",
+                xml_escape(&annotations.join(", "))
+            ));
         }
+        output.push_str("</tbody>\n</table>\n");
+        output
+    }
 
-        // Join all annotations with commas
-        output.push_str(&annotations.join(", "));
+    /// A day's `<td>`, styled like its terminal cell
+    fn html_day(&self, date: NaiveDate) -> String {
+        let style = self.day_style(date);
+        let mut css = Vec::new();
+        if let Some(Color::Rgb(bg)) = style.get_bg_color() {
+            css.push(format!("background:{}", css_hex(bg)));
+        }
+        match style.get_fg_color() {
+            Some(Color::Rgb(fg)) => css.push(format!("color:{}", css_hex(fg))),
+            Some(Color::Ansi(AnsiColor::Black)) => css.push("color:black".to_string()),
+            _ => {}
+        }
+        let effects = style.get_effects();
+        let decorations: Vec<&str> = [
+            (Effects::UNDERLINE, "underline"),
+            (Effects::STRIKETHROUGH, "line-through"),
+        ]
+        .iter()
+        .filter(|(effect, _)| effects.contains(*effect))
+        .map(|(_, name)| *name)
+        .collect();
+        if !decorations.is_empty() {
+            css.push(format!("text-decoration:{}", decorations.join(" ")));
+        }
+        if effects.contains(Effects::BOLD) {
+            css.push("font-weight:bold".to_string());
+        }
 
-        output
+        let mut attributes = String::new();
+        if effects.contains(Effects::DIMMED) {
+            attributes.push_str(" class=\"dimmed\"");
+        }
+        if !css.is_empty() {
+            attributes.push_str(&format!(" style=\"{}\"", css.join(";")));
+        }
+        format!("<td{}>{:02}</td>", attributes, date.day())
     }
 
-    fn separator_to_string(&self, layout: &WeekLayout, current_month: Option<u32>) -> String {
-        let mut output = String::new();
-        output.push_str("│             ├");
+    /// Week labels and annotations, one row per week
+    fn svg_weeks(&self, grid_right: usize) -> String {
+        let mut output = String::from("<g class=\"weeks\">\n");
+        let mut details_queue: Vec<(NaiveDate, DateDetail)> = Vec::new();
+        let mut shown_ranges: Vec<usize> = Vec::new();
 
-        let mut first_bar_idx = None;
-        for (idx, &date) in layout.dates.iter().enumerate() {
-            let in_month = date.year() == self.calendar.year && Some(date.month()) == current_month;
-            let prev_in_month = if idx > 0 {
-                let prev_date = layout.dates[idx - 1];
-                prev_date.year() == self.calendar.year && Some(prev_date.month()) == current_month
-            } else {
-                false
-            };
+        for (row, layout) in self.calendar.weeks().enumerate() {
+            let week_start = layout.dates[0];
+            let y = SVG_TOP + row * SVG_CELL + SVG_CELL / 2 + 4;
 
-            if in_month && !prev_in_month {
-                first_bar_idx = Some(idx);
+            output.push_str(&format!(
+                "<text x=\"4\" y=\"{}\">{}</text>\n",
+                y,
+                xml_escape(&self.week_label(row as i32 + 1, week_start))
+            ));
+
+            self.collect_details(&layout, &mut details_queue);
+            let annotations =
+                self.annotations_to_string(&layout, &mut details_queue, &mut shown_ranges);
+            if !annotations.is_empty() {
+                output.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\">{}</text>\n",
+                    grid_right + SVG_ANNOTATION_GAP,
+                    y,
+                    xml_escape(&annotations)
+                ));
             }
         }
 
-        if let Some(bar_idx) = first_bar_idx {
-            if bar_idx > 0 {
-                let dashes = (bar_idx - 1) * 5 + 4;
-                output.push_str(&format!("{:─<width$}┘", "", width = dashes));
-                let spaces = (DAYS_IN_WEEK - bar_idx) * 5 - 1;
-                output.push_str(&format!("{: <width$}│\n", "", width = spaces));
-            } else {
-                output.push_str("───────────────────────────────┤│\n");
+        output.push_str("</g>\n");
+        output
+    }
+
+    /// A single day cell: background, day number and past-date strike
+    fn svg_day(&self, first_week: NaiveDate, date: NaiveDate) -> String {
+        let x = SVG_LEFT + self.calendar.get_weekday_num(date) as usize * SVG_CELL;
+        let y = self.svg_row_y(first_week, date);
+        let is_weekend = self.calendar.weekend_display == WeekendDisplay::Dimmed
+            && self.calendar.is_weekend(date);
+
+        let (fill, text_fill) = match self
+            .calendar
+            .color_for(date)
+            .and_then(|color| self.palette.color_value(color))
+        {
+            Some(value) => {
+                let base = if self.calendar.dims_color(date) {
+                    value.dimmed
+                } else {
+                    value.normal
+                };
+                if self.calendar.range_caps && self.calendar.is_range_cap(date) {
+                    (css_hex(ColorValue::cap(base)), "#000000")
+                } else {
+                    (css_hex(base), "#000000")
+                }
             }
+            None if is_weekend => ("#ffffff".to_string(), "#999999"),
+            None => ("#ffffff".to_string(), "#000000"),
+        };
+        let stroke = if date == self.calendar.today {
+            "stroke=\"#000000\" stroke-width=\"2\""
         } else {
-            output.push_str("───────────────────────────────┤│\n");
-        }
+            "stroke=\"#dddddd\""
+        };
+        let weight = if self.calendar.is_important(date) {
+            " font-weight=\"bold\""
+        } else {
+            ""
+        };
 
+        let mut output = format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{cell}\" height=\"{cell}\" fill=\"{}\" {}/>\n",
+            x,
+            y,
+            fill,
+            stroke,
+            cell = SVG_CELL
+        );
+        output.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"{}\"{}>{:02}</text>\n",
+            x + SVG_CELL / 2,
+            y + SVG_CELL / 2 + 4,
+            text_fill,
+            weight,
+            date.day()
+        ));
+        if self.calendar.past_date_display.strikes_through() && date < self.calendar.today {
+            output.push_str(&format!(
+                "<line x1=\"{}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\" stroke=\"{}\"/>\n",
+                x + 6,
+                x + SVG_CELL - 6,
+                text_fill,
+                y = y + SVG_CELL / 2
+            ));
+        }
         output
     }
 
-    fn separator_before_month_to_string(
-        &self,
-        _current_layout: &WeekLayout,
-        _current_month: Option<u32>,
-        next_layout: &WeekLayout,
-    ) -> String {
-        let mut output = String::new();
-        if let Some((next_month_start_idx, _)) = next_layout.month_start_idx {
-            if next_month_start_idx == 0 {
-                output.push_str("│             ├");
-                output.push_str(&format!("{:─<width$}┤", "", width = CALENDAR_WIDTH));
-            } else {
-                output.push_str("│             │");
-                let spaces_before = (next_month_start_idx - 1) * 5 + 4;
-                output.push_str(&format!("{: <width$}┌", "", width = spaces_before));
-                let dashes = (DAYS_IN_WEEK - 1 - next_month_start_idx) * 5 + 4;
-                output.push_str(&format!("{:─<width$}┤", "", width = dashes));
-            }
+    fn svg_row_y(&self, first_week: NaiveDate, date: NaiveDate) -> usize {
+        let row =
+            (self.calendar.week_start_of(date) - first_week).num_days() as usize / DAYS_IN_WEEK;
+        SVG_TOP + row * SVG_CELL
+    }
+
+    /// The same renderer with colors turned off, for plain-text output
+    fn plain(&self) -> Self {
+        CalendarRenderer {
+            calendar: self.calendar,
+            palette: self.palette.clone(),
+            colors_enabled: false,
+        }
+    }
+
+    /// Get the filtered date range based on month filter
+    fn get_filtered_date_range(&self, filter: &MonthFilter) -> (NaiveDate, NaiveDate) {
+        filter.get_date_range(self.calendar.year, self.calendar.today)
+    }
+
+    /// Width of the left column holding the week label and month name
+    fn left_width(&self) -> usize {
+        let heat = if self.calendar.week_heat {
+            HEAT_WIDTH
         } else {
-            output.push_str("│             │");
-            output.push_str(&format!("{: <width$}", "", width = DAYS_IN_WEEK * 4 + 3));
+            0
+        };
+        heat + self.calendar.week_label_format.width() + MONTH_LABEL_WIDTH
+    }
+
+    fn header_width(&self) -> usize {
+        self.left_width() + 1 + CALENDAR_WIDTH
+    }
+
+    /// Whether to draw with ASCII only, for `--border-style ascii`
+    fn ascii(&self) -> bool {
+        self.calendar.border_style == BorderStyle::Ascii
+    }
+
+    /// The glyphs of the `--border-style`
+    fn border_chars(&self) -> BorderChars {
+        BorderChars::for_style(self.calendar.border_style)
+    }
+
+    /// The glyphs for lines between weeks, which face the other way when
+    /// `--reverse` draws the newest week first
+    fn separator_chars(&self) -> BorderChars {
+        let chars = self.border_chars();
+        if self.calendar.reverse {
+            chars.flipped()
+        } else {
+            chars
         }
+    }
 
-        output.push('\n');
+    fn week_label(&self, week_num: i32, first_day: NaiveDate) -> String {
+        self.calendar
+            .week_label_format
+            .label(self.calendar.week_number(week_num, first_day))
+    }
+
+    /// The left column with no label, e.g. for separator rows
+    fn blank_left(&self) -> String {
+        format!(
+            "{}{: <width$}",
+            self.border_chars().vertical,
+            "",
+            width = self.left_width()
+        )
+    }
+
+    fn header_to_string(&self) -> String {
+        let b = self.border_chars();
+        let rule = rule(b.horizontal, self.header_width());
+        let mut output = format!("{}{}{}\n", b.top_left, rule, b.top_right);
+
+        // Center the title
+        let title = truncate_display(&self.calendar.title(), self.header_width());
+        output.push_str(&format!(
+            "{}{}{}\n",
+            b.vertical,
+            center(&title, self.header_width()),
+            b.vertical
+        ));
+        if let Some(subtitle) = self.calendar.subtitle.as_deref().filter(|s| !s.is_empty()) {
+            let subtitle = truncate_display(subtitle, self.header_width());
+            output.push_str(&format!(
+                "{}{}{}\n",
+                b.vertical,
+                center(&subtitle, self.header_width()),
+                b.vertical
+            ));
+        }
+
+        output.push_str(&format!("{}{}{}\n", b.tee_right, rule, b.tee_left));
+        output.push_str(&self.blank_left());
+        output.push(' ');
+        let days: Vec<String> = self
+            .calendar
+            .week_start
+            .days()
+            .iter()
+            .map(|&day| self.calendar.locale.weekday(day).to_string())
+            .collect();
+        output.push_str(&format!("{} {}\n", days.join("  "), b.vertical));
         output
     }
 
-    fn print_header(&self) {
-        print!("{}", self.header_to_string());
+    fn weeks_to_string(&self) -> String {
+        self.filtered_weeks_to_string(&self.calendar.month_filter)
     }
 
-    fn print_weeks(&self) {
-        let (start_date, end_date) = self.get_filtered_date_range();
+    fn filtered_weeks_to_string(&self, filter: &MonthFilter) -> String {
+        let (start_date, _) = self.get_filtered_date_range(filter);
 
-        let mut current_date = self.align_to_week_start(start_date);
-        let mut week_num = 1;
         let mut current_month: Option<u32> = None;
 
         let mut details_queue: Vec<(NaiveDate, DateDetail)> = Vec::new();
         let mut shown_ranges: Vec<usize> = Vec::new();
 
         let mut is_first_month = true;
+        let mut top_border = String::new();
+        // Each week's lines, and the separator drawn below it
+        let mut weeks_out: Vec<(WeekLayout, String, String)> = Vec::new();
+
+        let mut weeks = (1..).zip(self.calendar.weeks_for(filter)).peekable();
+        while let Some((week_num, layout)) = weeks.next() {
+            let next_layout = weeks.peek().map(|(_, next)| next);
+            let mut output = String::new();
+
+            let month_label =
+                self.update_current_month(&layout, start_date, &mut current_month, is_first_month);
+            if is_first_month {
+                top_border = self.month_border_to_string(&layout, current_month);
+                is_first_month = false;
+            }
 
-        while current_date <= end_date {
-            let layout = WeekLayout::new(current_date);
+            self.collect_details(&layout, &mut details_queue);
 
-            // Skip weeks that don't contain filtered months
-            if !self.should_render_week(&layout) {
-                current_date = current_date
-                    .checked_add_signed(chrono::Duration::days(DAYS_IN_WEEK as i64))
-                    .unwrap();
-                continue;
+            let annotations =
+                self.keyed_week_annotations(&layout, &mut details_queue, &mut shown_ranges);
+            let footnotes = self.footnotes(&annotations);
+            let annotations: Vec<String> = annotations
+                .into_iter()
+                .map(|(day, text)| match footnote_on(&footnotes, day) {
+                    Some(marker) => format!("{} {}", marker, text),
+                    None => text,
+                })
+                .collect();
+            let row = self.week_row_to_string(week_num, &layout, month_label, &footnotes);
+            match self.calendar.annotation_position {
+                AnnotationPosition::Right => {
+                    let lines = self.wrap_annotations(&annotations, self.header_width() + 2);
+                    for (idx, line) in lines.iter().enumerate() {
+                        if idx == 0 {
+                            output.push_str(&row);
+                        } else {
+                            output.push_str(&self.continuation_row());
+                        }
+                        output.push_str(line);
+                        output.push('\n');
+                    }
+                }
+                AnnotationPosition::Below => {
+                    output.push_str(&row);
+                    output.push('\n');
+                    output.push_str(&self.annotation_line(&annotations));
+                }
+                AnnotationPosition::Left => {
+                    output.push_str(&self.annotation_line(&annotations));
+                    output.push_str(&row);
+                    output.push('\n');
+                }
             }
 
-            let next_week_date = current_date
-                .checked_add_signed(chrono::Duration::days(DAYS_IN_WEEK as i64))
-                .unwrap();
-            let next_layout = WeekLayout::new(next_week_date);
+            // The filtered range alone decides where rendering stops, so
+            // windows can run into the next year
+            let mut separator = String::new();
+            if let Some(next_layout) = next_layout {
+                // Weeks left out by --only-active
+                if layout.dates[DAYS_IN_WEEK - 1].succ_opt() != Some(next_layout.dates[0]) {
+                    separator = self.gap_separator_to_string(&layout, next_layout);
+                } else if let Some((idx, _)) = layout.month_start_idx {
+                    if idx > 0 {
+                        separator = self.separator_to_string(&layout, current_month);
+                    }
+                } else if next_layout.month_start_idx.is_some() {
+                    separator =
+                        self.separator_before_month_to_string(&layout, current_month, next_layout);
+                }
+            }
+            weeks_out.push((layout, output, separator));
+        }
 
-            if let Some((_, month)) = layout.month_start_idx {
-                current_month = Some(month);
-                if is_first_month {
-                    self.print_month_border(&layout, current_month);
-                    is_first_month = false;
+        let (Some((first, _, _)), Some((last, _, _))) = (weeks_out.first(), weeks_out.last())
+        else {
+            return String::new();
+        };
+        let mut output = String::new();
+        if self.calendar.reverse {
+            // Newest week first: the borders mirror top to bottom
+            output.push_str(&self.month_border_to_string(last, None));
+            for (idx, (_, week, _)) in weeks_out.iter().enumerate().rev() {
+                output.push_str(week);
+                if let Some((_, _, separator)) = idx.checked_sub(1).map(|prev| &weeks_out[prev]) {
+                    output.push_str(separator);
                 }
             }
+            output.push_str(&self.bottom_border_to_string(first));
+        } else {
+            output.push_str(&top_border);
+            for (_, week, separator) in &weeks_out {
+                output.push_str(week);
+                output.push_str(separator);
+            }
+            output.push_str(&self.bottom_border_to_string(last));
+        }
+        output
+    }
 
-            self.collect_details(&layout, &mut details_queue);
+    /// The line closing the grid below `layout`, stepping down where a
+    /// month starts partway through the week
+    fn bottom_border_to_string(&self, layout: &WeekLayout) -> String {
+        let mut month_boundary_idx = None;
+        for (idx, &date) in layout.dates.iter().enumerate() {
+            if idx > 0 {
+                let prev_date = layout.dates[idx - 1];
+                if date.month() != prev_date.month() || date.year() != prev_date.year() {
+                    month_boundary_idx = Some(idx);
+                    break;
+                }
+            }
+        }
+
+        let b = self.border_chars();
+        let left = rule(b.horizontal, self.left_width());
+        if let Some(boundary_idx) = month_boundary_idx {
+            let dashes_before = (boundary_idx - 1) * 5 + 4;
+            let dashes_after = (DAYS_IN_WEEK - boundary_idx) * 5 - 1;
+            format!(
+                "{}{}{}{}{}{}{}\n",
+                b.bottom_left,
+                left,
+                b.tee_up,
+                rule(b.horizontal, dashes_before),
+                b.tee_up,
+                rule(b.horizontal, dashes_after),
+                b.bottom_right
+            )
+        } else {
+            format!(
+                "{}{}{}{}{}\n",
+                b.bottom_left,
+                left,
+                b.tee_up,
+                rule(b.horizontal, CALENDAR_WIDTH),
+                b.bottom_right
+            )
+        }
+    }
+
+    fn month_border_to_string(&self, layout: &WeekLayout, _current_month: Option<u32>) -> String {
+        let b = self.border_chars();
+        let mut output = String::new();
+        if let Some((idx, _)) = layout.month_start_idx {
+            if idx > 0 {
+                output.push_str(&self.blank_left());
+                output.push(b.top_left);
+                let dashes_before = (idx - 1) * 5 + 4;
+                output.push_str(&rule(b.horizontal, dashes_before));
+                output.push(b.tee_down);
+                let dashes_after = (DAYS_IN_WEEK - idx) * 5 - 1;
+                output.push_str(&rule(b.horizontal, dashes_after));
+                output.push(b.tee_left);
+                output.push('\n');
+            }
+        }
+        output
+    }
 
-            self.print_week_row(week_num, &layout, current_month);
+    fn week_row_to_string(
+        &self,
+        week_num: i32,
+        layout: &WeekLayout,
+        month_label: Option<u32>,
+        footnotes: &[(NaiveDate, char)],
+    ) -> String {
+        let b = self.border_chars();
+        let mut output = String::new();
+        let month_name = month_label.map_or("", |month| self.calendar.locale.month_name(month));
 
-            self.print_annotations(&layout, &mut details_queue, &mut shown_ranges);
+        output.push(b.vertical);
+        if self.calendar.week_heat {
+            output.push_str(&self.heat_cell(layout));
+            output.push(' ');
+        }
+        output.push_str(&format!(
+            "{:<label$} {:<9}",
+            self.week_label(week_num, layout.get_first_date()),
+            month_name,
+            label = self.calendar.week_label_format.width()
+        ));
 
-            println!();
+        output.push(b.vertical);
 
-            let is_last_week =
-                next_week_date.year() > self.calendar.year || next_week_date > end_date;
+        for (idx, &date) in layout.dates.iter().enumerate() {
+            let is_month_boundary = if idx > 0 {
+                let prev_date = layout.dates[idx - 1];
+                date.month() != prev_date.month() || date.year() != prev_date.year()
+            } else {
+                false
+            };
 
-            if is_last_week {
-                let mut month_boundary_idx = None;
-                for (idx, &date) in layout.dates.iter().enumerate() {
-                    if idx > 0 {
-                        let prev_date = layout.dates[idx - 1];
-                        if date.month() != prev_date.month() || date.year() != prev_date.year() {
-                            month_boundary_idx = Some(idx);
-                            break;
-                        }
-                    }
-                }
+            if is_month_boundary {
+                output.push(b.vertical);
+            }
 
-                if let Some(boundary_idx) = month_boundary_idx {
-                    let dashes_before = (boundary_idx - 1) * 5 + 4;
-                    let dashes_after = (DAYS_IN_WEEK - boundary_idx) * 5 - 1;
-                    println!(
-                        "└{:─<13}┴{:─<before$}┴{:─<after$}┘",
-                        "",
-                        "",
-                        "",
-                        before = dashes_before,
-                        after = dashes_after
-                    );
+            let marked = !self.colors_enabled && self.calendar.is_important(date);
+            let flag = if self.calendar.milestone_on(date).is_some() {
+                if self.ascii() {
+                    ASCII_MILESTONE_FLAG
                 } else {
-                    println!("└{:─<13}┴{:─<width$}┘", "", "", width = CALENDAR_WIDTH);
+                    MILESTONE_FLAG
                 }
-            } else if let Some((idx, _)) = layout.month_start_idx {
-                if idx > 0 {
-                    self.print_separator(&layout, current_month);
+            } else if marked {
+                '*'
+            } else {
+                ' '
+            };
+            output.push(flag);
+            output.push_str(&self.styled_day(date, &format!("{:02}", date.day())));
+
+            // Important days are wrapped in markers when colors are off,
+            // unless a footnote takes the place of the closing one
+            output.push(match footnote_on(footnotes, Some(date)) {
+                Some(marker) => marker,
+                None if marked => '*',
+                None => ' ',
+            });
+            if idx < 6 {
+                let next_date = layout.dates[idx + 1];
+                let next_is_boundary =
+                    date.month() != next_date.month() || date.year() != next_date.year();
+                if !next_is_boundary {
+                    output.push(' ');
                 }
-            } else if next_layout.month_start_idx.is_some()
-                && next_week_date <= end_date
-                && next_week_date.year() == self.calendar.year
-            {
-                self.print_separator_before_month(&layout, current_month, &next_layout);
             }
+        }
 
-            current_date = next_week_date;
-            week_num += 1;
+        output.push(b.vertical);
+        output
+    }
 
-            if current_date.year() > self.calendar.year {
-                break;
-            }
+    /// With `--footnotes`, a marker for each day of the week that has
+    /// annotations, numbered from the start of the week
+    fn footnotes(&self, annotations: &[(Option<NaiveDate>, String)]) -> Vec<(NaiveDate, char)> {
+        if !self.calendar.footnotes {
+            return Vec::new();
         }
+        let mut days: Vec<NaiveDate> = annotations.iter().filter_map(|(day, _)| *day).collect();
+        days.sort();
+        days.dedup();
+        let markers = if self.ascii() {
+            ASCII_FOOTNOTE_MARKERS
+        } else {
+            FOOTNOTE_MARKERS
+        };
+        days.into_iter().zip(markers).collect()
     }
 
-    fn align_to_week_start(&self, date: NaiveDate) -> NaiveDate {
-        let mut aligned = date;
-        while self.calendar.get_weekday_num(aligned) != 0 {
-            aligned = aligned.pred_opt().unwrap();
+    /// `text` for `date` in its cell style, or plain when colors are off
+    pub(crate) fn styled_day(&self, date: NaiveDate, text: &str) -> String {
+        let style = self.day_style(date);
+        if !self.colors_enabled || style == Style::new() {
+            text.to_string()
+        } else {
+            format!("{}{}{}", style.render(), text, style.render_reset())
         }
-        aligned
     }
 
-    fn get_date_color(&self, date: NaiveDate) -> Option<String> {
-        // In work mode, never color weekends
-        if self.calendar.color_mode == ColorMode::Work
-            && (date.weekday() == Weekday::Sat || date.weekday() == Weekday::Sun)
-        {
-            return None;
+    /// Events, milestones, and ranges falling in the week
+    fn week_event_count(&self, layout: &WeekLayout) -> usize {
+        let week_start = layout.dates[0];
+        let week_end = layout.dates[DAYS_IN_WEEK - 1];
+        let in_week = |date: &NaiveDate| *date >= week_start && *date <= week_end;
+
+        self.calendar
+            .details_iter()
+            .filter(|(date, _)| in_week(date))
+            .count()
+            + self
+                .calendar
+                .milestones
+                .iter()
+                .filter(|milestone| in_week(&milestone.date))
+                .count()
+            + self
+                .calendar
+                .ranges
+                .iter()
+                .filter(|range| range.start <= week_end && range.end >= week_start)
+                .count()
+    }
+
+    /// A one-character cell shaded by how busy the week is
+    fn heat_cell(&self, layout: &WeekLayout) -> String {
+        let level = self.week_event_count(layout).min(HEAT_LEVELS.len() - 1);
+        if level == 0 {
+            return " ".to_string();
+        }
+        if !self.colors_enabled {
+            let levels = if self.ascii() {
+                ASCII_HEAT_LEVELS
+            } else {
+                HEAT_LEVELS
+            };
+            return levels[level].to_string();
         }
 
-        // Check if date has a specific color
-        if let Some(detail) = self.calendar.details.get(&date) {
-            if let Some(color) = &detail.color {
-                return Some(color.clone());
-            }
+        // Blend from a faint to a strong orange as the week fills up
+        let strength = level as u32 * 255 / (HEAT_LEVELS.len() as u32 - 1);
+        let blend = |from: u8, to: u8| {
+            (u32::from(from) + (u32::from(to) - u32::from(from)) * strength / 255) as u8
+        };
+        let color = RgbColor(blend(90, 255), blend(60, 143), blend(40, 64));
+        let style = Style::new().bg_color(Some(Color::Rgb(color)));
+        format!("{} {}", style.render(), style.render_reset())
+    }
+
+    /// Resolve the full style of a date's cell.
+    ///
+    /// This is independent of whether colors are enabled; callers decide
+    /// whether to emit it.
+    pub fn day_style(&self, date: NaiveDate) -> Style {
+        let today = self.calendar.today;
+        let is_today = date == today;
+        let is_past = self.calendar.past_date_display.strikes_through() && date < today;
+
+        let is_weekend = self.calendar.weekend_display == WeekendDisplay::Dimmed
+            && self.calendar.is_weekend(date);
+
+        let mut effects = Effects::new();
+        if is_past {
+            effects |= ColorCodes::strikethrough();
+        }
+        if is_today {
+            effects |= ColorCodes::underline();
+        }
+        if self.calendar.is_important(date) {
+            effects |= ColorCodes::bold();
         }
+        effects |= self.calendar.effects_on(date);
 
-        // Check if date is in a range
-        for range in &self.calendar.ranges {
-            if date >= range.start && date <= range.end {
-                return Some(range.color.clone());
+        if let Some((color, source)) = self.calendar.date_color(date) {
+            if source == ColorSource::Range {
+                effects |= self.calendar.range_effect.unwrap_or_default();
             }
+            let dimmed = self.calendar.dims_color(date);
+            let style = if self.calendar.range_caps && self.calendar.is_range_cap(date) {
+                ColorCodes::get_cap_bg_color(&self.palette, color, dimmed)
+            } else if dimmed {
+                ColorCodes::get_dimmed_bg_color(&self.palette, color)
+            } else {
+                ColorCodes::get_bg_color(&self.palette, color)
+            };
+            style
+                .fg_color(ColorCodes::black_text().get_fg_color())
+                .effects(effects)
+        } else {
+            if is_weekend && self.calendar.background.dimming_reads_well() {
+                effects |= ColorCodes::dim();
+            } else if is_weekend {
+                // Dim text washes out on light backgrounds, use a solid gray
+                return Style::new()
+                    .fg_color(Some(Color::Rgb(LIGHT_BG_WEEKEND_TEXT)))
+                    .effects(effects);
+            }
+            Style::new().effects(effects)
         }
+    }
 
-        None
+    fn annotations_to_string(
+        &self,
+        layout: &WeekLayout,
+        details_queue: &mut Vec<(NaiveDate, DateDetail)>,
+        shown_ranges: &mut Vec<usize>,
+    ) -> String {
+        self.week_annotations(layout, details_queue, shown_ranges)
+            .join(", ")
     }
 
-    fn print_month_border(&self, layout: &WeekLayout, current_month: Option<u32>) {
-        print!("{}", self.month_border_to_string(layout, current_month));
+    /// Each annotation for the week, in the order they are shown
+    fn week_annotations(
+        &self,
+        layout: &WeekLayout,
+        details_queue: &mut Vec<(NaiveDate, DateDetail)>,
+        shown_ranges: &mut Vec<usize>,
+    ) -> Vec<String> {
+        self.keyed_week_annotations(layout, details_queue, shown_ranges)
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect()
     }
 
-    fn collect_details(
+    /// Each annotation for the week with the day of the row it belongs to,
+    /// in the order they are shown
+    fn keyed_week_annotations(
         &self,
         layout: &WeekLayout,
         details_queue: &mut Vec<(NaiveDate, DateDetail)>,
-    ) {
-        for &date in &layout.dates {
-            if let Some(detail) = self.calendar.details.get(&date) {
-                if !details_queue.iter().any(|(d, _)| d == &date) {
-                    details_queue.push((date, detail.clone()));
-                }
+        shown_ranges: &mut Vec<usize>,
+    ) -> Vec<(Option<NaiveDate>, String)> {
+        if self.calendar.hide_annotations {
+            return Vec::new();
+        }
+        let week_start = layout.dates[0];
+        let week_end = layout.dates[DAYS_IN_WEEK - 1];
+        let mut annotations = Vec::new();
+        let style = self.calendar.annotation_style;
+
+        if self.calendar.jdn_annotate {
+            annotations.push((None, format!("JD {}", jdn(week_start))));
+        }
+
+        // Each event starting in or overlapping this week, with its sort key
+        let mut events: Vec<(NaiveDate, AnnotationKind, Option<NaiveTime>, String)> = Vec::new();
+
+        details_queue.retain(|(detail_date, detail)| {
+            let in_week = *detail_date >= week_start && *detail_date <= week_end;
+            if in_week {
+                events.push((
+                    *detail_date,
+                    AnnotationKind::Detail,
+                    detail.time,
+                    self.styled_annotation(
+                        detail.color.as_deref(),
+                        detail.important,
+                        detail.effects,
+                        &self.detail_text(*detail_date, detail),
+                    ),
+                ));
+            }
+            !in_week
+        });
+
+        // Count down to any milestones in this week
+        for milestone in &self.calendar.milestones {
+            if milestone.date >= week_start && milestone.date <= week_end {
+                let text = format!(
+                    "{} - {}",
+                    style.format_date(milestone.date),
+                    milestone.countdown(self.calendar.today)
+                );
+                events.push((
+                    milestone.date,
+                    AnnotationKind::Milestone,
+                    None,
+                    self.styled_annotation(Some(milestone.color()), false, Effects::new(), &text),
+                ));
+            }
+        }
+
+        // Collect all ranges that overlap with this week
+        for (idx, range) in self.calendar.ranges.iter().enumerate() {
+            if !shown_ranges.contains(&idx) && range.start <= week_end && range.end >= week_start {
+                let text = match &range.description {
+                    Some(desc) => format!("{} - {}", self.range_dates(range), desc),
+                    None => self.range_dates(range),
+                };
+                let text = match self.range_length_to_string(range) {
+                    Some(length) => format!("{} ({})", text, length),
+                    None => text,
+                };
+                events.push((
+                    range.start,
+                    AnnotationKind::Range,
+                    None,
+                    self.styled_annotation(
+                        Some(&range.color),
+                        range.important,
+                        range.effects,
+                        &text,
+                    ),
+                ));
+                shown_ranges.push(idx);
             }
         }
+
+        // List events by start date, then by kind and time of day. Ranges
+        // from earlier weeks belong to the week's first day.
+        events.sort_by_key(|(date, kind, time, _)| (*date, *kind, *time));
+        annotations.extend(
+            events
+                .into_iter()
+                .map(|(date, _, _, text)| (Some(date.max(week_start)), text)),
+        );
+
+        annotations
+    }
+
+    /// Annotations on their own lines, indented to line up with the day columns
+    fn annotation_line(&self, annotations: &[String]) -> String {
+        let prefix = format!("{}{} ", self.blank_left(), self.border_chars().vertical);
+        self.wrap_annotations(annotations, self.left_width() + 3)
+            .iter()
+            .filter(|line| !line.is_empty())
+            .map(|line| format!("{}{}\n", prefix, line))
+            .collect()
     }
 
-    fn print_week_row(&self, week_num: i32, layout: &WeekLayout, _current_month: Option<u32>) {
-        let month_name = if let Some((_, month)) = layout.month_start_idx {
-            MonthInfo::from_month(month).name
+    /// An empty week row, for annotations continued past the first line
+    fn continuation_row(&self) -> String {
+        let vertical = self.border_chars().vertical;
+        format!(
+            "{}{}{: <width$}{}",
+            self.blank_left(),
+            vertical,
+            "",
+            vertical,
+            width = CALENDAR_WIDTH
+        )
+    }
+
+    /// `annotations` joined with commas and wrapped into lines that fit the
+    /// columns left after `used`, when a width is set
+    fn wrap_annotations(&self, annotations: &[String], used: usize) -> Vec<String> {
+        let width = self
+            .calendar
+            .max_annotation_width
+            .or_else(|| self.calendar.width.map(|width| width.saturating_sub(used)));
+        match width {
+            Some(width) => wrap_items(annotations, width),
+            None => vec![annotations.join(", ")],
+        }
+    }
+
+    /// A detail's annotation, e.g. "03/14 09:30 - Standup"
+    fn detail_text(&self, date: NaiveDate, detail: &DateDetail) -> String {
+        let day = self.calendar.annotation_style.format_date(date);
+        let text = match detail.time {
+            Some(time) => format!("{} {} - {}", day, time.format("%H:%M"), detail.description),
+            None => format!("{} - {}", day, detail.description),
+        };
+        if self.calendar.relative {
+            format!("{} ({})", text, relative_days(date, self.calendar.today))
         } else {
-            ""
+            text
+        }
+    }
+
+    /// The length of `range` for its annotation, e.g. "7 days, 5 working"
+    fn range_length_to_string(&self, range: &DateRange) -> Option<String> {
+        let days = pluralize(range.duration_days() as usize, "day");
+        let workdays = self.calendar.range_workdays(range);
+        match (self.calendar.range_duration, self.calendar.range_workdays) {
+            (true, true) => Some(format!("{}, {} working", days, workdays)),
+            (true, false) => Some(days),
+            (false, true) => Some(pluralize(workdays, "working day")),
+            (false, false) => None,
+        }
+    }
+
+    /// "03/04 to 03/08", with the year on an end outside the calendar's year
+    fn range_dates(&self, range: &DateRange) -> String {
+        let style = self.calendar.annotation_style;
+        format!(
+            "{} to {}",
+            style.format_date_in(range.start, self.calendar.year),
+            style.format_date_in(range.end, self.calendar.year)
+        )
+    }
+
+    fn styled_annotation(
+        &self,
+        color: Option<&str>,
+        important: bool,
+        effects: Effects,
+        text: &str,
+    ) -> String {
+        let text = if important {
+            text.to_uppercase()
+        } else {
+            text.to_string()
         };
+        if !self.colors_enabled {
+            return text;
+        }
 
-        if !month_name.is_empty() {
-            print!("│W{:02} {:<9}", week_num, month_name);
+        let style = match color {
+            Some(color) => ColorCodes::get_bg_color(&self.palette, color)
+                .fg_color(ColorCodes::black_text().get_fg_color()),
+            None => Style::new(),
+        };
+        let style = if important {
+            style.effects(ColorCodes::bold() | effects)
         } else {
-            print!("│W{:02}          ", week_num);
+            style.effects(effects)
+        };
+
+        if style == Style::new() {
+            text
+        } else {
+            format!("{}{}{}", style.render(), text, style.render_reset())
         }
+    }
 
-        print!("│");
+    fn separator_to_string(&self, layout: &WeekLayout, current_month: Option<u32>) -> String {
+        let b = self.separator_chars();
+        let mut output = String::new();
+        output.push_str(&self.blank_left());
+        output.push(b.tee_right);
 
+        let mut first_bar_idx = None;
         for (idx, &date) in layout.dates.iter().enumerate() {
-            let is_month_boundary = if idx > 0 {
+            // Month numbers are unique within a single week, even across years
+            let in_month = Some(date.month()) == current_month;
+            let prev_in_month = if idx > 0 {
                 let prev_date = layout.dates[idx - 1];
-                date.month() != prev_date.month() || date.year() != prev_date.year()
+                Some(prev_date.month()) == current_month
             } else {
                 false
             };
 
-            if is_month_boundary {
-                print!("│");
+            if in_month && !prev_in_month {
+                first_bar_idx = Some(idx);
             }
+        }
 
-            let today = chrono::Local::now().date_naive();
-            let is_today = date == today;
-            let is_past =
-                self.calendar.past_date_display == PastDateDisplay::Strikethrough && date < today;
-
-            let is_weekend = self.calendar.weekend_display == WeekendDisplay::Dimmed
-                && (date.weekday() == Weekday::Sat || date.weekday() == Weekday::Sun);
-
-            if let Some(color) = self.get_date_color(date) {
-                let mut style = if is_weekend {
-                    ColorCodes::get_dimmed_bg_color(&color)
-                } else {
-                    ColorCodes::get_bg_color(&color)
-                };
-
-                if ColorCodes::is_color_disabled() {
-                    print!(" {:02}", date.day());
-                } else {
-                    style = style.fg_color(ColorCodes::black_text().get_fg_color());
+        match first_bar_idx.filter(|&idx| idx > 0) {
+            Some(bar_idx) => {
+                let dashes = (bar_idx - 1) * 5 + 4;
+                output.push_str(&rule(b.horizontal, dashes));
+                output.push(b.bottom_right);
+                let spaces = (DAYS_IN_WEEK - bar_idx) * 5 - 1;
+                output.push_str(&format!("{: <width$}{}\n", "", b.vertical, width = spaces));
+            }
+            None => {
+                output.push_str(&rule(b.horizontal, 31));
+                output.push(b.tee_left);
+                output.push(b.vertical);
+                output.push('\n');
+            }
+        }
 
-                    let mut effects = Effects::new();
-                    if is_past {
-                        effects |= ColorCodes::strikethrough();
-                    }
-                    if is_today {
-                        effects |= ColorCodes::underline();
-                    }
-                    style = style.effects(effects);
+        output
+    }
 
-                    print!(
-                        " {}{:02}{}",
-                        style.render(),
-                        date.day(),
-                        style.render_reset()
-                    );
-                }
-            } else if ColorCodes::is_color_disabled() {
-                print!(" {:02}", date.day());
+    fn separator_before_month_to_string(
+        &self,
+        _current_layout: &WeekLayout,
+        _current_month: Option<u32>,
+        next_layout: &WeekLayout,
+    ) -> String {
+        let b = self.separator_chars();
+        let mut output = String::new();
+        if let Some((next_month_start_idx, _)) = next_layout.month_start_idx {
+            if next_month_start_idx == 0 {
+                output.push_str(&self.blank_left());
+                output.push(b.tee_right);
+                output.push_str(&rule(b.horizontal, CALENDAR_WIDTH));
+                output.push(b.tee_left);
             } else {
-                let mut style = Style::new();
-                let mut effects = Effects::new();
+                output.push_str(&self.blank_left());
+                output.push(b.vertical);
+                let spaces_before = (next_month_start_idx - 1) * 5 + 4;
+                output.push_str(&format!("{: <width$}", "", width = spaces_before));
+                output.push(b.top_left);
+                let dashes = (DAYS_IN_WEEK - 1 - next_month_start_idx) * 5 + 4;
+                output.push_str(&rule(b.horizontal, dashes));
+                output.push(b.tee_left);
+            }
+        } else {
+            output.push_str(&self.blank_left());
+            output.push(b.vertical);
+            output.push_str(&format!("{: <width$}", "", width = DAYS_IN_WEEK * 4 + 3));
+        }
 
-                if is_past {
-                    effects |= ColorCodes::strikethrough();
-                }
-                if is_today {
-                    effects |= ColorCodes::underline();
-                }
-                if is_weekend {
-                    effects |= ColorCodes::dim();
-                }
+        output.push('\n');
+        output
+    }
 
-                style = style.effects(effects);
+    /// A line across the weeks skipped between `layout` and `next_layout`,
+    /// joining the month boundaries of both
+    fn gap_separator_to_string(&self, layout: &WeekLayout, next_layout: &WeekLayout) -> String {
+        let bar = |layout: &WeekLayout| {
+            layout
+                .month_start_idx
+                .and_then(|(idx, _)| idx.checked_sub(1))
+                .map(|idx| idx * 5 + 4)
+        };
+        let b = self.separator_chars();
+        let (above, below) = (bar(layout), bar(next_layout));
+        let line: String = (0..CALENDAR_WIDTH)
+            .map(|pos| match (above == Some(pos), below == Some(pos)) {
+                (true, true) => b.cross,
+                (true, false) => b.tee_up,
+                (false, true) => b.tee_down,
+                (false, false) => b.horizontal,
+            })
+            .collect();
+        format!(
+            "{}{}{}{}\n",
+            self.blank_left(),
+            b.tee_right,
+            line,
+            b.tee_left
+        )
+    }
 
-                if effects == Effects::new() {
-                    print!(" {:02}", date.day());
-                } else {
-                    print!(
-                        " {}{:02}{}",
-                        style.render(),
-                        date.day(),
-                        style.render_reset()
-                    );
-                }
-            }
+    /// Track the month being rendered and return the month to label this week with.
+    ///
+    /// A week is labelled when a month starts in it, or when it is the first
+    /// rendered week and the filtered range opens partway through a month.
+    fn update_current_month(
+        &self,
+        layout: &WeekLayout,
+        start_date: NaiveDate,
+        current_month: &mut Option<u32>,
+        is_first_week: bool,
+    ) -> Option<u32> {
+        if let Some((_, month)) = layout.month_start_idx {
+            *current_month = Some(month);
+            return *current_month;
+        }
 
-            if idx < 6 {
-                let next_date = layout.dates[idx + 1];
-                let next_is_boundary =
-                    date.month() != next_date.month() || date.year() != next_date.year();
-                if next_is_boundary {
-                    print!(" ");
-                } else {
-                    print!("  ");
-                }
-            } else {
-                print!(" ");
-            }
+        if is_first_week {
+            let first_date = layout
+                .dates
+                .iter()
+                .copied()
+                .find(|date| *date >= start_date)
+                .unwrap_or(layout.dates[0]);
+            *current_month = Some(first_date.month());
+            return *current_month;
         }
 
-        print!("│");
+        None
     }
 
-    fn print_annotations(
+    fn collect_details(
         &self,
         layout: &WeekLayout,
         details_queue: &mut Vec<(NaiveDate, DateDetail)>,
-        shown_ranges: &mut Vec<usize>,
     ) {
-        let week_start = layout.dates[0];
-        let week_end = layout.dates[DAYS_IN_WEEK - 1];
-        let mut first = true;
-
-        // Collect and print all details that occur in this week
-        let mut details_to_remove = Vec::new();
-        for (i, (detail_date, detail)) in details_queue.iter().enumerate() {
-            if *detail_date >= week_start && *detail_date <= week_end {
-                if !first {
-                    print!(", ");
-                }
-                first = false;
-
-                if ColorCodes::is_color_disabled() {
-                    print!("{} - {}", detail_date.format("%m/%d"), detail.description);
-                } else if let Some(color) = &detail.color {
-                    let style = ColorCodes::get_bg_color(color)
-                        .fg_color(ColorCodes::black_text().get_fg_color());
-                    print!(
-                        "{}{} - {}{}",
-                        style.render(),
-                        detail_date.format("%m/%d"),
-                        detail.description,
-                        style.render_reset()
-                    );
-                } else {
-                    print!("{} - {}", detail_date.format("%m/%d"), detail.description);
+        if self.calendar.hide_annotations {
+            return;
+        }
+        for &date in &layout.dates {
+            if let Some(events) = self.calendar.details.get(&date) {
+                if !details_queue.iter().any(|(d, _)| d == &date) {
+                    details_queue.extend(events.iter().map(|detail| (date, detail.clone())));
                 }
-                details_to_remove.push(i);
             }
         }
-        // Remove details in reverse order to maintain indices
-        for &i in details_to_remove.iter().rev() {
-            details_queue.remove(i);
-        }
+    }
+}
 
-        // Collect and print all ranges that overlap with this week
-        for (idx, range) in self.calendar.ranges.iter().enumerate() {
-            if !shown_ranges.contains(&idx) && range.start <= week_end && range.end >= week_start {
-                if !first {
-                    print!(", ");
-                }
-                first = false;
-
-                if ColorCodes::is_color_disabled() {
-                    if let Some(desc) = &range.description {
-                        print!(
-                            "{} to {} - {}",
-                            range.start.format("%m/%d"),
-                            range.end.format("%m/%d"),
-                            desc
-                        );
-                    } else {
-                        print!(
-                            "{} to {}",
-                            range.start.format("%m/%d"),
-                            range.end.format("%m/%d")
-                        );
-                    }
-                } else {
-                    let style = ColorCodes::get_bg_color(&range.color)
-                        .fg_color(ColorCodes::black_text().get_fg_color());
-
-                    if let Some(desc) = &range.description {
-                        print!(
-                            "{}{} to {} - {}{}",
-                            style.render(),
-                            range.start.format("%m/%d"),
-                            range.end.format("%m/%d"),
-                            desc,
-                            style.render_reset()
-                        );
-                    } else {
-                        print!(
-                            "{}{} to {}{}",
-                            style.render(),
-                            range.start.format("%m/%d"),
-                            range.end.format("%m/%d"),
-                            style.render_reset()
-                        );
-                    }
-                }
-                shown_ranges.push(idx);
-            }
+/// Tiebreaker for annotations of events starting on the same date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum AnnotationKind {
+    Detail,
+    Milestone,
+    Range,
+}
+
+/// `width` copies of `c`, e.g. a run of horizontal border
+fn rule(c: char, width: usize) -> String {
+    c.to_string().repeat(width)
+}
+
+/// The footnote marker of `day`, if it has one
+fn footnote_on(footnotes: &[(NaiveDate, char)], day: Option<NaiveDate>) -> Option<char> {
+    footnotes
+        .iter()
+        .find(|(date, _)| Some(*date) == day)
+        .map(|(_, marker)| *marker)
+}
+
+/// The glyphs of one [`BorderStyle`]. Every style has a glyph in each
+/// position, so all of them line up the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorderChars {
+    pub horizontal: char,
+    pub vertical: char,
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    /// `├`, a vertical line with a branch to the right
+    pub tee_right: char,
+    /// `┤`
+    pub tee_left: char,
+    /// `┬`
+    pub tee_down: char,
+    /// `┴`
+    pub tee_up: char,
+    pub cross: char,
+    /// The outer box drawn by `--framed`
+    pub frame: FrameChars,
+}
+
+/// The glyphs of the `--framed` box around the calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameChars {
+    pub horizontal: char,
+    pub vertical: char,
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    /// The left end of the line above the stats, e.g. `╟`
+    pub divider_left: char,
+    /// The right end of the line above the stats, e.g. `╢`
+    pub divider_right: char,
+    /// Between the stats, e.g. `·`
+    pub separator: char,
+}
+
+impl FrameChars {
+    pub const DOUBLE: Self = Self {
+        horizontal: '═',
+        vertical: '║',
+        top_left: '╔',
+        top_right: '╗',
+        bottom_left: '╚',
+        bottom_right: '╝',
+        divider_left: '╟',
+        divider_right: '╢',
+        separator: '·',
+    };
+
+    pub const ASCII: Self = Self {
+        horizontal: '=',
+        vertical: '|',
+        top_left: '+',
+        top_right: '+',
+        bottom_left: '+',
+        bottom_right: '+',
+        divider_left: '+',
+        divider_right: '+',
+        separator: '-',
+    };
+}
+
+impl BorderChars {
+    pub const SHARP: Self = Self {
+        horizontal: '─',
+        vertical: '│',
+        top_left: '┌',
+        top_right: '┐',
+        bottom_left: '└',
+        bottom_right: '┘',
+        tee_right: '├',
+        tee_left: '┤',
+        tee_down: '┬',
+        tee_up: '┴',
+        cross: '┼',
+        frame: FrameChars::DOUBLE,
+    };
+
+    pub const ROUNDED: Self = Self {
+        top_left: '╭',
+        top_right: '╮',
+        bottom_left: '╰',
+        bottom_right: '╯',
+        ..Self::SHARP
+    };
+
+    pub const DOUBLE: Self = Self {
+        horizontal: '═',
+        vertical: '║',
+        top_left: '╔',
+        top_right: '╗',
+        bottom_left: '╚',
+        bottom_right: '╝',
+        tee_right: '╠',
+        tee_left: '╣',
+        tee_down: '╦',
+        tee_up: '╩',
+        cross: '╬',
+        // The divider is a double line too, so it meets the sides in a tee
+        frame: FrameChars {
+            divider_left: '╠',
+            divider_right: '╣',
+            ..FrameChars::DOUBLE
+        },
+    };
+
+    pub const ASCII: Self = Self {
+        horizontal: '-',
+        vertical: '|',
+        top_left: '+',
+        top_right: '+',
+        bottom_left: '+',
+        bottom_right: '+',
+        tee_right: '+',
+        tee_left: '+',
+        tee_down: '+',
+        tee_up: '+',
+        cross: '+',
+        frame: FrameChars::ASCII,
+    };
+
+    pub fn for_style(style: BorderStyle) -> Self {
+        match style {
+            BorderStyle::Sharp => Self::SHARP,
+            BorderStyle::Rounded => Self::ROUNDED,
+            BorderStyle::Double => Self::DOUBLE,
+            BorderStyle::Ascii => Self::ASCII,
         }
     }
 
-    fn print_separator(&self, layout: &WeekLayout, current_month: Option<u32>) {
-        print!("{}", self.separator_to_string(layout, current_month));
+    /// This set mirrored top to bottom, with top and bottom corners and
+    /// tees swapped
+    fn flipped(self) -> Self {
+        Self {
+            top_left: self.bottom_left,
+            top_right: self.bottom_right,
+            bottom_left: self.top_left,
+            bottom_right: self.top_right,
+            tee_down: self.tee_up,
+            tee_up: self.tee_down,
+            ..self
+        }
     }
+}
 
-    fn print_separator_before_month(
-        &self,
-        current_layout: &WeekLayout,
-        current_month: Option<u32>,
-        next_layout: &WeekLayout,
-    ) {
-        print!(
-            "{}",
-            self.separator_before_month_to_string(current_layout, current_month, next_layout)
-        );
+/// Parse `#rrggbb` (the `#` is optional) into a color
+pub fn parse_hex_color(hex: &str) -> Result<RgbColor, String> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    let channel = |i: usize| {
+        digits
+            .get(i..i + 2)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+    };
+    match (digits.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) => Ok(RgbColor(r, g, b)),
+        _ => Err(format!("Invalid hex color: {} (expected #rrggbb)", hex)),
     }
 }
+
+/// `color` as a CSS hex color, e.g. `#f07178`, for the SVG and HTML outputs
+fn css_hex(color: RgbColor) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+}
+
+/// Escape `text` for a Markdown table cell
+fn markdown_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace('\n', " ")
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
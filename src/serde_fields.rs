@@ -0,0 +1,90 @@
+/// [`anstyle::Effects`] as a list of names, e.g. `["bold", "underline"]`
+pub mod effects {
+    use crate::config::parse_effects;
+    use anstyle::Effects;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    const NAMES: [(Effects, &str); 4] = [
+        (Effects::BOLD, "bold"),
+        (Effects::ITALIC, "italic"),
+        (Effects::UNDERLINE, "underline"),
+        (Effects::STRIKETHROUGH, "strikethrough"),
+    ];
+
+    pub fn names(effects: Effects) -> Vec<&'static str> {
+        NAMES
+            .iter()
+            .filter(|(effect, _)| effects.contains(*effect))
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    pub fn serialize<S: Serializer>(effects: &Effects, serializer: S) -> Result<S::Ok, S::Error> {
+        names(*effects).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Effects, D::Error> {
+        let names = Vec::<String>::deserialize(deserializer)?;
+        parse_effects(&names).map_err(D::Error::custom)
+    }
+
+    /// An optional set of effects, `None` when absent
+    pub mod option {
+        use anstyle::Effects;
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            effects: &Option<Effects>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            effects.map(super::names).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Effects>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapper(#[serde(with = "super")] Effects);
+            Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(effects)| effects))
+        }
+    }
+}
+
+/// An optional start time as `HH:MM`
+pub mod time {
+    use crate::config::parse_time;
+    use chrono::NaiveTime;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        time: &Option<NaiveTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        time.map(|time| time.format("%H:%M").to_string())
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<NaiveTime>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|time| parse_time(&time).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// An [`anstyle::RgbColor`] as `#rrggbb`
+pub mod rgb {
+    use crate::rendering::parse_hex_color;
+    use anstyle::RgbColor;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &RgbColor, serializer: S) -> Result<S::Ok, S::Error> {
+        let RgbColor(r, g, b) = *color;
+        serializer.serialize_str(&format!("#{:02x}{:02x}{:02x}", r, g, b))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<RgbColor, D::Error> {
+        parse_hex_color(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,153 @@
+use crate::models::TerminalBackground;
+use std::time::Duration;
+
+/// How long to wait for the terminal to answer the background query
+pub const QUERY_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Ask the terminal for its background color with the OSC 11 query.
+///
+/// Returns `None` when stdin/stdout are not terminals or the terminal does
+/// not answer within `timeout`.
+#[cfg(unix)]
+pub fn query_background(timeout: Duration) -> Option<(u8, u8, u8)> {
+    use std::fs::OpenOptions;
+    use std::io::{IsTerminal, Read, Write};
+    use std::os::unix::io::AsRawFd;
+
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return None;
+    }
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    let fd = tty.as_raw_fd();
+
+    // Read the reply without echoing it or waiting for a newline
+    let mut original = unsafe { std::mem::zeroed::<libc::termios>() };
+    if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+        return None;
+    }
+    let mut raw = original;
+    raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+    raw.c_cc[libc::VMIN] = 0;
+    raw.c_cc[libc::VTIME] = 0;
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw) } != 0 {
+        return None;
+    }
+
+    let mut response = Vec::new();
+    if tty
+        .write_all(b"\x1b]11;?\x1b\\")
+        .and_then(|_| tty.flush())
+        .is_ok()
+    {
+        let deadline = std::time::Instant::now() + timeout;
+        let mut buf = [0u8; 64];
+        while let Some(remaining) = deadline.checked_duration_since(std::time::Instant::now()) {
+            let mut pollfd = libc::pollfd {
+                fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let ready = unsafe { libc::poll(&mut pollfd, 1, remaining.as_millis() as i32) };
+            if ready <= 0 {
+                break;
+            }
+            match tty.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => response.extend_from_slice(&buf[..n]),
+            }
+            // The reply ends with BEL or ST (ESC \)
+            if response.ends_with(b"\x07") || response.ends_with(b"\x1b\\") {
+                break;
+            }
+        }
+    }
+
+    unsafe { libc::tcsetattr(fd, libc::TCSANOW, &original) };
+    parse_osc11_response(&String::from_utf8_lossy(&response))
+}
+
+#[cfg(not(unix))]
+pub fn query_background(_timeout: Duration) -> Option<(u8, u8, u8)> {
+    None
+}
+
+/// Parse an OSC 11 reply such as `ESC ] 11 ; rgb:1e1e/1e1e/2e2e BEL`
+pub fn parse_osc11_response(response: &str) -> Option<(u8, u8, u8)> {
+    let start = response.find("rgb:")? + "rgb:".len();
+    let body = response[start..].trim_end_matches(['\x07', '\x1b', '\\']);
+    let mut channels = body.split('/').map(|channel| {
+        // Channels have 1-4 hex digits; scale each to 8 bits
+        let digits = channel.get(..channel.len().min(4))?;
+        let value = u32::from_str_radix(digits, 16).ok()?;
+        let max = (1u32 << (4 * digits.len())) - 1;
+        Some((value * 255 / max) as u8)
+    });
+    let rgb = (channels.next()??, channels.next()??, channels.next()??);
+    Some(rgb)
+}
+
+/// Detect the terminal background, falling back to dark when unknown
+pub fn detect_background() -> TerminalBackground {
+    query_background(QUERY_TIMEOUT)
+        .map(|(r, g, b)| TerminalBackground::from_rgb(r, g, b))
+        .unwrap_or(TerminalBackground::Dark)
+}
+
+/// Columns in the terminal stdout is attached to, or `None` when stdout is
+/// not a terminal
+pub fn terminal_width() -> Option<usize> {
+    use std::io::IsTerminal;
+
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    terminal_size::terminal_size().map(|(terminal_size::Width(width), _)| usize::from(width))
+}
+
+/// The pager `--page` runs: `$PAGER`, or `less -R` so colors survive
+pub fn pager_command() -> String {
+    std::env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| "less -R".to_string())
+}
+
+/// Start [`pager_command`] reading from a pipe. `None` when stdout is not
+/// a terminal or the pager can't be started, to print directly instead.
+pub fn spawn_pager() -> Option<std::process::Child> {
+    use std::io::IsTerminal;
+
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    let command = pager_command();
+    let mut words = command.split_whitespace();
+    std::process::Command::new(words.next()?)
+        .args(words)
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .ok()
+}
+
+/// Write `output` to `pager` and wait for the reader to close it. Quitting
+/// the pager before the end is not an error.
+pub fn page(mut pager: std::process::Child, output: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    // Dropping stdin closes the pipe, so the pager sees the end
+    let written = pager
+        .stdin
+        .take()
+        .expect("pager stdin is piped")
+        .write_all(output);
+    pager.wait()?;
+    match written {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        written => written,
+    }
+}
@@ -0,0 +1,17 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+/// Parse an IANA timezone name such as `America/New_York`
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.parse::<Tz>().map_err(|_| {
+        format!(
+            "Invalid timezone: '{}'. Use an IANA name like 'Europe/Berlin'",
+            name
+        )
+    })
+}
+
+/// The calendar date at `now` as observed in `tz`
+pub fn today_in(tz: Tz, now: DateTime<Utc>) -> NaiveDate {
+    now.with_timezone(&tz).date_naive()
+}
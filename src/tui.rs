@@ -0,0 +1,177 @@
+use crate::config::CalendarConfig;
+use crate::formatting::display_width;
+use crate::models::{CalendarOptions, MonthFilter};
+use crate::rendering::CalendarRenderer;
+use chrono::{Datelike, NaiveDate};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, queue, style::Print, terminal};
+use std::io::{self, Write};
+
+const HELP: &str = "←/→ month  ↑/↓ year  g today  q quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavKey {
+    NextMonth,
+    PrevMonth,
+    NextYear,
+    PrevYear,
+    Today,
+    Quit,
+}
+
+impl NavKey {
+    pub fn from_key_event(key: KeyEvent) -> Option<Self> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Self::Quit);
+        }
+        match key.code {
+            KeyCode::Right | KeyCode::Char('l') => Some(Self::NextMonth),
+            KeyCode::Left | KeyCode::Char('h') => Some(Self::PrevMonth),
+            KeyCode::Down | KeyCode::Char('j') => Some(Self::NextYear),
+            KeyCode::Up | KeyCode::Char('k') => Some(Self::PrevYear),
+            KeyCode::Char('g') => Some(Self::Today),
+            KeyCode::Char('q') | KeyCode::Esc => Some(Self::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// The month being browsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NavState {
+    pub year: i32,
+    pub month: u32,
+    pub quit: bool,
+}
+
+impl NavState {
+    pub fn new(year: i32, month: u32) -> Self {
+        NavState {
+            year,
+            month,
+            quit: false,
+        }
+    }
+
+    /// Apply a key press; `today` is where [`NavKey::Today`] jumps to
+    pub fn apply(&mut self, key: NavKey, today: NaiveDate) {
+        match key {
+            NavKey::NextMonth if self.month == 12 => {
+                self.year += 1;
+                self.month = 1;
+            }
+            NavKey::NextMonth => self.month += 1,
+            NavKey::PrevMonth if self.month == 1 => {
+                self.year -= 1;
+                self.month = 12;
+            }
+            NavKey::PrevMonth => self.month -= 1,
+            NavKey::NextYear => self.year += 1,
+            NavKey::PrevYear => self.year -= 1,
+            NavKey::Today => {
+                self.year = today.year();
+                self.month = today.month();
+            }
+            NavKey::Quit => self.quit = true,
+        }
+    }
+}
+
+/// Raw mode on the alternate screen, restored on drop even if drawing fails
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Browse the calendar a month at a time until the user quits
+pub fn run(config: &CalendarConfig, options: &CalendarOptions, start: NavState) -> io::Result<()> {
+    let _guard = TerminalGuard::enter()?;
+    let mut stdout = io::stdout();
+    let mut state = start;
+
+    draw(&mut stdout, config, options, state)?;
+    while !state.quit {
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                if let Some(nav) = NavKey::from_key_event(key) {
+                    state.apply(nav, options.today);
+                    if !state.quit {
+                        draw(&mut stdout, config, options, state)?;
+                    }
+                }
+            }
+            Event::Resize(_, _) => draw(&mut stdout, config, options, state)?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn draw<W: Write>(
+    w: &mut W,
+    config: &CalendarConfig,
+    options: &CalendarOptions,
+    state: NavState,
+) -> io::Result<()> {
+    let mut month_options = options.clone();
+    month_options.month_filter = MonthFilter::Single(state.month);
+    let calendar = crate::build_calendar(state.year, month_options, config.clone());
+
+    let mut grid = Vec::new();
+    CalendarRenderer::new(&calendar).render_to_writer(&mut grid)?;
+    let grid = String::from_utf8_lossy(&grid);
+    let grid: Vec<&str> = grid.lines().collect();
+
+    // Side panel with the events of the month
+    let (start_date, end_date) = calendar.date_range();
+    let mut panel = vec!["Events".to_string()];
+    let mut listed: Vec<String> = Vec::new();
+    for date in start_date.iter_days().take_while(|date| *date <= end_date) {
+        for event in calendar.resolve_day(date).events {
+            // Ranges repeat on every day they cover; list them once
+            if !listed.contains(&event) {
+                panel.push(format!("{}  {}", date.format("%m/%d"), event));
+                listed.push(event);
+            }
+        }
+    }
+    if panel.len() == 1 {
+        panel.push("(none)".to_string());
+    }
+
+    let grid_width = grid
+        .iter()
+        .map(|line| display_width(line))
+        .max()
+        .unwrap_or(0);
+    let (_, rows) = terminal::size()?;
+    let rows = usize::from(rows).saturating_sub(1);
+
+    queue!(w, terminal::Clear(terminal::ClearType::All))?;
+    for row in 0..grid.len().max(panel.len()).min(rows) {
+        let left = grid.get(row).copied().unwrap_or("");
+        let right = panel.get(row).map_or("", String::as_str);
+        let line = format!(
+            "{}{: <pad$}  {}",
+            left,
+            "",
+            right,
+            pad = grid_width - display_width(left)
+        );
+        queue!(w, cursor::MoveTo(0, row as u16), Print(line))?;
+    }
+    queue!(w, cursor::MoveTo(0, rows as u16), Print(HELP))?;
+    w.flush()
+}
@@ -0,0 +1,210 @@
+use anstyle::{Effects, RgbColor};
+use chrono::{Datelike, NaiveDate, Weekday};
+use compact_calendar_cli::models::{
+    Calendar, CalendarBuilder, ColorMode, DateDetail, DateRange, EventFilter, MonthFilter,
+    WeekStart, WeekendDisplay,
+};
+use compact_calendar_cli::output::{render_to, OutputFormat};
+use compact_calendar_cli::rendering::CalendarRenderer;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn detail(description: &str, color: &str) -> DateDetail {
+    DateDetail {
+        description: description.to_string(),
+        color: Some(color.to_string()),
+        important: false,
+        pinned: false,
+        severity: None,
+        time: None,
+        effects: Effects::new(),
+        dim_weekends: None,
+    }
+}
+
+fn sprint() -> DateRange {
+    DateRange {
+        start: date(2025, 3, 10),
+        end: date(2025, 3, 14),
+        color: "blue".to_string(),
+        description: Some("Sprint".to_string()),
+        important: false,
+        pinned: false,
+        effects: Effects::new(),
+        dim_weekends: None,
+    }
+}
+
+#[test]
+fn test_builder_renders_without_config() {
+    let calendar = CalendarBuilder::new()
+        .year(2025)
+        .today(date(2025, 1, 1))
+        .month_filter(MonthFilter::Single(3))
+        .week_start(WeekStart::Sunday)
+        .weekend_display(WeekendDisplay::Normal)
+        .add_detail(date(2025, 3, 18), detail("Launch", "red"))
+        .add_range(sprint())
+        .build();
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    assert!(output.contains("COMPACT CALENDAR 2025"));
+    assert!(output.contains("Sun  Mon  Tue  Wed  Thu  Fri  Sat │"));
+    assert!(output.contains("03/10 to 03/14 - Sprint"), "{}", output);
+    assert!(output.contains("03/18 - Launch"), "{}", output);
+}
+
+#[test]
+fn test_builder_defaults() {
+    let today = date(2031, 6, 1);
+    let calendar = CalendarBuilder::new().today(today).build();
+
+    // The year follows today unless one is given
+    assert_eq!(calendar.year, 2031);
+    assert_eq!(calendar.week_start, WeekStart::Monday);
+    assert_eq!(calendar.month_filter, MonthFilter::All);
+    assert!(calendar.details.is_empty());
+}
+
+#[test]
+fn test_builder_event_filter_and_colors() {
+    let calendar = CalendarBuilder::new()
+        .year(2025)
+        .event_filter(EventFilter::OnlyRanges)
+        .add_detail(date(2025, 3, 18), detail("Launch", "brand"))
+        .add_range(sprint())
+        .add_color("brand", RgbColor(10, 20, 30))
+        .build();
+
+    assert!(calendar.details.is_empty());
+    assert_eq!(calendar.ranges, vec![sprint()]);
+    assert_eq!(calendar.colors["brand"].normal, RgbColor(10, 20, 30));
+}
+
+#[test]
+fn test_color_for_follows_precedence() {
+    let calendar = CalendarBuilder::new()
+        .year(2025)
+        .add_detail(date(2025, 3, 12), detail("Launch", "red"))
+        .add_detail(
+            date(2025, 3, 13),
+            DateDetail {
+                color: None,
+                ..detail("Standup", "")
+            },
+        )
+        .add_range(sprint())
+        .build();
+
+    // An event's own color wins over the range around it
+    assert_eq!(calendar.color_for(date(2025, 3, 12)), Some("red"));
+    assert_eq!(calendar.color_for(date(2025, 3, 13)), Some("blue"));
+    assert_eq!(calendar.color_for(date(2025, 3, 20)), None);
+}
+
+#[test]
+fn test_work_mode_counts_colored_days_as_taken() {
+    let builder = CalendarBuilder::new()
+        .year(2025)
+        .month_filter(MonthFilter::Single(3))
+        .add_detail(date(2025, 3, 18), detail("Launch", "red"))
+        .add_range(sprint());
+
+    let stats = builder.clone().build().stats();
+    assert_eq!(
+        (stats.workdays, stats.weekend_days, stats.range_days),
+        (21, 10, 5)
+    );
+
+    let stats = builder.color_mode(ColorMode::Work).build().stats();
+    assert_eq!(stats.workdays, 15);
+}
+
+#[test]
+fn test_weekday_highlight_sits_under_events_and_ranges() {
+    let builder = CalendarBuilder::new()
+        .year(2025)
+        .month_filter(MonthFilter::Single(3))
+        .add_detail(date(2025, 3, 5), detail("Launch", "red"))
+        .add_range(sprint())
+        .highlight_weekday(Weekday::Wed, "green")
+        .highlight_weekday(Weekday::Sat, "purple");
+
+    let calendar = builder.clone().build();
+    assert_eq!(calendar.color_for(date(2025, 3, 5)), Some("red"));
+    assert_eq!(calendar.color_for(date(2025, 3, 12)), Some("blue"));
+    assert_eq!(calendar.color_for(date(2025, 3, 19)), Some("green"));
+    assert_eq!(calendar.color_for(date(2025, 3, 22)), Some("purple"));
+    // Highlighted weekdays are still working days
+    assert_eq!(calendar.stats().workdays, 21);
+
+    let calendar = builder.color_mode(ColorMode::Work).build();
+    assert_eq!(calendar.color_for(date(2025, 3, 22)), None);
+    assert_eq!(calendar.color_for(date(2025, 3, 19)), Some("green"));
+    assert_eq!(calendar.stats().workdays, 15);
+}
+
+#[test]
+fn test_years_at_the_date_bounds_render_without_panicking() {
+    for (year, month) in [(NaiveDate::MIN.year(), 1), (NaiveDate::MAX.year(), 12)] {
+        let calendar = CalendarBuilder::new()
+            .year(year)
+            .month_filter(MonthFilter::Single(month))
+            .week_start(WeekStart::Sunday)
+            .build();
+        let output = CalendarRenderer::new(&calendar).render_to_string();
+        assert!(output.contains(&format!("COMPACT CALENDAR {}", year)));
+
+        let mut svg = Vec::new();
+        render_to(&calendar, OutputFormat::Svg, false, &mut svg).unwrap();
+    }
+
+    // Every week of the supported years is whole
+    let last = *Calendar::supported_years().end();
+    let calendar = CalendarBuilder::new()
+        .year(last)
+        .month_filter(MonthFilter::Single(12))
+        .week_start(WeekStart::Sunday)
+        .build();
+    let last_week = calendar.weeks().last().unwrap();
+    assert_eq!(last_week.dates[0], date(last, 12, 31));
+    assert_eq!(last_week.dates[6], date(last + 1, 1, 6));
+}
+
+#[test]
+fn test_events_on_lists_details_and_covering_ranges() {
+    let offsite = DateRange {
+        start: date(2025, 3, 12),
+        end: date(2025, 3, 20),
+        color: "green".to_string(),
+        description: Some("Offsite".to_string()),
+        ..sprint()
+    };
+    let calendar = CalendarBuilder::new()
+        .year(2025)
+        .add_detail(date(2025, 3, 13), detail("Launch", "red"))
+        .add_detail(date(2025, 3, 13), detail("Retro", "blue"))
+        .add_range(sprint())
+        .add_range(offsite.clone())
+        .build();
+
+    let events = calendar.events_on(date(2025, 3, 13));
+    assert_eq!(events.date, date(2025, 3, 13));
+    let descriptions: Vec<&str> = events
+        .details
+        .iter()
+        .map(|detail| detail.description.as_str())
+        .collect();
+    assert_eq!(descriptions, ["Launch", "Retro"]);
+    // Ranges come back in config order
+    assert_eq!(events.ranges, [&sprint(), &offsite]);
+
+    let events = calendar.events_on(date(2025, 3, 18));
+    assert!(events.details.is_empty());
+    assert_eq!(events.ranges, [&offsite]);
+
+    let events = calendar.events_on(date(2025, 4, 1));
+    assert!(events.details.is_empty() && events.ranges.is_empty());
+}
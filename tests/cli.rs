@@ -0,0 +1,981 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn calendar_cli() -> Command {
+    let mut command = calendar_cli_without_month();
+    command.args(["--month", "1"]);
+    command
+}
+
+fn calendar_cli_without_month() -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"));
+    command
+        .env("NO_COLOR", "1")
+        .args(["--config", "tests/fixtures/empty.toml"]);
+    command
+}
+
+#[test]
+fn test_year_from_environment() {
+    let output = calendar_cli()
+        .env("CALENDAR_YEAR", "2030")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("COMPACT CALENDAR 2030"), "{}", stdout);
+}
+
+#[test]
+fn test_year_flag_overrides_environment() {
+    let output = calendar_cli()
+        .env("CALENDAR_YEAR", "2030")
+        .args(["--year", "2031"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("COMPACT CALENDAR 2031"), "{}", stdout);
+}
+
+#[test]
+fn test_week_label_format() {
+    let output = calendar_cli()
+        .args(["--year", "2026", "--week-label-format", "KW{n:02}"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("│KW01 January  │"), "{}", stdout);
+}
+
+#[test]
+fn test_week_label_format_requires_number() {
+    let output = calendar_cli()
+        .args(["--year", "2026", "--week-label-format", "Week"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("{n}"), "{}", stderr);
+}
+
+#[test]
+fn test_year_progress_of_a_past_year() {
+    let output = calendar_cli()
+        .args(["--year", "2024", "--year-progress"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let line = stdout
+        .lines()
+        .find(|line| line.ends_with("% of 2024"))
+        .unwrap();
+    assert_eq!(line, "[████████████████████] 100% of 2024");
+}
+
+#[test]
+fn test_only_ranges_and_only_details_conflict() {
+    let output = calendar_cli()
+        .args(["--only-ranges", "--only-details"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_rest_of_year() {
+    let output = calendar_cli_without_month()
+        .args(["--year", "2024", "--today", "2024-07-17", "--rest-of-year"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let weeks: Vec<&str> = stdout.lines().filter(|l| l.starts_with("│W")).collect();
+    assert!(
+        weeks[0].starts_with("│W29 July     │ 15   16   17"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("June"));
+    assert!(weeks.last().unwrap().contains("30   31"), "{}", stdout);
+    assert!(stdout.contains("December"));
+}
+
+#[test]
+fn test_rest_of_year_after_year_end() {
+    let output = calendar_cli_without_month()
+        .args(["--year", "2023", "--today", "2024-07-17", "--rest-of-year"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("Nothing left to show"), "{}", stdout);
+}
+
+#[test]
+fn test_jdn_annotate() {
+    let output = calendar_cli()
+        .args(["--year", "2024", "--jdn-annotate"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // The first week of 2024 starts on Monday 01/01
+    assert!(
+        stdout.contains("│W01 January  │ 01   02   03   04   05   06   07 │JD 2460311"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_relative_annotations() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .env("NO_COLOR", "1")
+        .args(["--config", "tests/fixtures/simple.toml", "--year", "2025"])
+        .args(["--month", "feb", "--today", "2025-02-10", "--relative"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("02/14 - Valentine's Day (in 4 days)"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("02/01 - Q1 Review Due (9 days ago)"),
+        "{}",
+        stdout
+    );
+    // Ranges keep their plain annotation
+    assert!(
+        stdout.contains("02/10 to 02/16 - Sprint Planning,"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_year_out_of_range() {
+    let output = calendar_cli_without_month()
+        .args(["--year", "262142"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr,
+        "Error: Year 262142 is out of range (-262142 to 262141)\n"
+    );
+}
+
+#[test]
+fn test_from_to_window() {
+    let output = calendar_cli_without_month()
+        .args(["--from", "2024-03-15", "--to", "2024-06-14"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("COMPACT CALENDAR 2024"), "{}", stdout);
+    let weeks: Vec<&str> = stdout.lines().filter(|l| l.starts_with("│W")).collect();
+    assert!(
+        weeks[0].contains("March    │ 11   12   13   14   15"),
+        "{}",
+        stdout
+    );
+    assert!(
+        weeks.last().unwrap().contains(" 10   11   12   13   14 "),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("July"));
+}
+
+#[test]
+fn test_from_after_to_is_rejected() {
+    let output = calendar_cli_without_month()
+        .args(["--from", "2024-06-14", "--to", "2024-03-15"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("--from 2024-06-14 is after --to 2024-03-15"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_from_conflicts_with_month() {
+    let output = calendar_cli()
+        .args(["--from", "2024-03-15", "--to", "2024-06-14"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("cannot be used with"), "{}", stderr);
+}
+
+#[test]
+fn test_json_flag() {
+    let output = calendar_cli()
+        .args(["--year", "2024", "--json"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["year"], 2024);
+    assert_eq!(json["weeks"][0]["week"], 1);
+}
+
+#[test]
+fn test_legend() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .env("NO_COLOR", "1")
+        .args(["--config", "tests/fixtures/legend.toml"])
+        .args(["--year", "2024", "--month", "3", "--legend"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let legend = &stdout[stdout.find("Legend:").unwrap()..];
+    // In order of first use, once each, falling back to the color's name
+    assert_eq!(
+        legend.trim_end(),
+        "Legend:\n  ██ green\n  ██ blue - Travel\n  ██ red - Deadline"
+    );
+}
+
+#[test]
+fn test_overlapping_ranges_warn() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .env("NO_COLOR", "1")
+        .args(["--config", "tests/fixtures/overlap.toml", "--year", "2024"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr,
+        "Warning: ranges 2024-03-04 to 2024-03-15 (Sprint) and 2024-03-13 to 2024-03-20 (Trip) \
+         overlap; the first one colors the shared days\n"
+    );
+}
+
+#[test]
+fn test_overlapping_ranges_strict() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .env("NO_COLOR", "1")
+        .args(["--config", "tests/fixtures/overlap.toml", "--year", "2024"])
+        .arg("--strict")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("Error: ranges 2024-03-04"), "{}", stderr);
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_unknown_color_warns_and_errors_with_strict() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .env("NO_COLOR", "1")
+        .args([
+            "--config",
+            "tests/fixtures/typo_color.toml",
+            "--year",
+            "2024",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr, "Warning: Unknown color 'gren' on 2024-03-05\n");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .env("NO_COLOR", "1")
+        .args([
+            "--config",
+            "tests/fixtures/typo_color.toml",
+            "--year",
+            "2024",
+        ])
+        .arg("--strict")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr, "Error: Unknown color 'gren' on 2024-03-05\n");
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_holidays_flag() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .env("NO_COLOR", "1")
+        .args(["--config", "tests/fixtures/empty.toml", "--year", "2024"])
+        .args(["--holidays", "us", "--month", "nov"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("11/28 - Thanksgiving Day"), "{}", stdout);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .args(["--config", "tests/fixtures/empty.toml", "--holidays", "xx"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_weekend_flag() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .args([
+            "--config",
+            "tests/fixtures/empty.toml",
+            "--weekend",
+            "fri,sat",
+        ])
+        .args(["--year", "2024", "--json"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let days: Vec<&serde_json::Value> = json["weeks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|week| week["dates"].as_array().unwrap())
+        .collect();
+    // 2024-03-08 is a Friday, 03-10 a Sunday
+    let day = |date: &str| {
+        days.iter()
+            .find(|day| day["date"] == date)
+            .copied()
+            .unwrap()
+    };
+    assert_eq!(day("2024-03-08")["is_weekend"], true);
+    assert_eq!(day("2024-03-10")["is_weekend"], false);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .args([
+            "--config",
+            "tests/fixtures/empty.toml",
+            "--weekend",
+            "fri,xyz",
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Invalid weekday: xyz"), "{}", stderr);
+}
+
+fn colored(args: &[&str], env: &[(&str, &str)]) -> bool {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .env_remove("NO_COLOR")
+        .env_remove("FORCE_COLOR")
+        .envs(env.iter().copied())
+        .args(["--config", "tests/fixtures/simple.toml", "--year", "2024"])
+        .args(["--month", "mar", "--bg", "dark"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap().contains("\x1b[")
+}
+
+#[test]
+fn test_color_choice() {
+    // Piped output is plain unless asked for
+    assert!(!colored(&[], &[]));
+    assert!(colored(&["--color", "always"], &[]));
+    assert!(colored(&["--color", "always"], &[("NO_COLOR", "1")]));
+    assert!(!colored(&["--color", "never"], &[("FORCE_COLOR", "1")]));
+
+    assert!(colored(&[], &[("FORCE_COLOR", "1")]));
+    assert!(colored(&[], &[("FORCE_COLOR", "1"), ("NO_COLOR", "1")]));
+    assert!(!colored(&[], &[("FORCE_COLOR", "0")]));
+}
+
+#[test]
+fn test_year_grid_follows_color_choice() {
+    let grid = ["--year-grid", "2"];
+    assert!(colored(&[&grid[..], &["--color", "always"]].concat(), &[]));
+    assert!(!colored(&grid, &[("FORCE_COLOR", "0")]));
+    assert!(!colored(
+        &[&grid[..], &["--color", "never"]].concat(),
+        &[("FORCE_COLOR", "1")]
+    ));
+}
+
+#[test]
+fn test_invalid_color_choice() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .args([
+            "--config",
+            "tests/fixtures/empty.toml",
+            "--color",
+            "sometimes",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr,
+        "Error: Invalid color: 'sometimes'. Use one of: auto, always, never\n"
+    );
+}
+
+fn run_with_stdin(args: &[&str], stdin: &str) -> std::process::Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .env("NO_COLOR", "1")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_config_from_stdin() {
+    let config = "[dates.2024-03-05]\ndescription = \"Piped in\"\ncolor = \"red\"\n";
+    let output = run_with_stdin(
+        &["--config", "-", "--year", "2024", "--month", "mar"],
+        config,
+    );
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("03/05 - Piped in"), "{}", stdout);
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn test_invalid_config_from_stdin() {
+    let output = run_with_stdin(&["--config", "-"], "[dates\n");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("Error: Config from stdin: Failed to parse TOML config"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_missing_config_is_an_error() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .args(["--config", "tests/fixtures/missing.toml"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr,
+        "Error: tests/fixtures/missing.toml: not found (use --config to point elsewhere)\n"
+    );
+}
+
+#[test]
+fn test_missing_default_config_warns() {
+    // No calendar.toml in the fixtures directory
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .current_dir("tests/fixtures")
+        .env_remove("CALENDAR_CONFIG")
+        .env("NO_COLOR", "1")
+        .args(["--year", "2024", "--month", "1"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("Warning: calendar.toml not found, showing no events"),
+        "{}",
+        stderr
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("COMPACT CALENDAR 2024"), "{}", stdout);
+}
+
+#[test]
+fn test_malformed_config_reports_position() {
+    let dir =
+        std::env::temp_dir().join(format!("compact-calendar-malformed-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("calendar.toml");
+    std::fs::write(&path, "[dates.2024-03-05]\ndescription = \"Unclosed\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .arg("--config")
+        .arg(&path)
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with(&format!(
+            "Error: {}: Failed to parse TOML config: TOML parse error at line 2, column",
+            path.display()
+        )),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_quarter_flag() {
+    let output = calendar_cli_without_month()
+        .args(["--year", "2024", "--quarter", "2"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("April"), "{}", stdout);
+    assert!(stdout.contains("June"), "{}", stdout);
+    assert!(!stdout.contains("March"), "{}", stdout);
+    assert!(!stdout.contains("July"), "{}", stdout);
+
+    let output = calendar_cli_without_month()
+        .args(["--quarter", "5"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr, "Error: Quarter must be 1-4, got 5\n");
+}
+
+#[test]
+fn test_weeks_flag() {
+    let output = calendar_cli_without_month()
+        .args(["--year", "2026", "--weeks", "52-53"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("│W52 December │ 21 "), "{}", stdout);
+    assert!(stdout.contains("│W53 January  │ 28 "), "{}", stdout);
+    assert!(!stdout.contains("W51"), "{}", stdout);
+
+    // Every year shown needs the week
+    let output = calendar_cli_without_month()
+        .args(["--year", "2026", "--year", "2025", "--weeks", "53"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr, "Error: 2025 has 52 ISO weeks, got week 53\n");
+}
+
+#[test]
+fn test_weeks_flag_in_another_year() {
+    // Week 22 of 2026, not of the year today falls in
+    let output = calendar_cli_without_month()
+        .args(["--today", "2025-06-01", "--year", "2026", "--weeks", "22"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("│W22 May      │ 25 "), "{}", stdout);
+    assert_eq!(stdout.matches("│W").count(), 1, "{}", stdout);
+}
+
+#[test]
+fn test_only_active_flag() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .env("NO_COLOR", "1")
+        .args(["--config", "tests/fixtures/sparse.toml", "--year", "2026"])
+        .arg("--only-active")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("07/10 - Launch"), "{}", stdout);
+    assert!(!stdout.contains("September"), "{}", stdout);
+}
+
+#[test]
+fn test_page_prints_directly_when_not_a_terminal() {
+    let plain = calendar_cli().args(["--year", "2024"]).output().unwrap();
+    // Stdout is a pipe here, so the pager is never started
+    let paged = calendar_cli()
+        .env("PAGER", "false")
+        .args(["--year", "2024", "--page"])
+        .output()
+        .unwrap();
+
+    assert!(paged.status.success());
+    assert_eq!(paged.stdout, plain.stdout);
+}
+
+#[test]
+fn test_pager_quitting_early_is_not_an_error() {
+    let pager = Command::new("true")
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let output = vec![b'x'; 1 << 20];
+    assert!(compact_calendar_cli::terminal::page(pager, &output).is_ok());
+}
+
+#[test]
+fn test_several_years() {
+    let output = calendar_cli()
+        .args(["--year", "2024", "--year", "2025"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let first = stdout.find("COMPACT CALENDAR 2024").unwrap();
+    let second = stdout.find("COMPACT CALENDAR 2025").unwrap();
+    assert!(first < second, "{}", stdout);
+    // Each year is drawn whole, with a blank line before the next header
+    assert!(stdout.contains("┘\n\n┌"), "{}", stdout);
+    assert_eq!(stdout.matches("COMPACT CALENDAR").count(), 2);
+
+    let range = calendar_cli()
+        .args(["--years", "2024-2025"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8(range.stdout).unwrap(), stdout);
+}
+
+#[test]
+fn test_several_years_expand_recurring_events() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .env("NO_COLOR", "1")
+        .args([
+            "--config",
+            "tests/fixtures/simple.toml",
+            "--years",
+            "2024-2025",
+        ])
+        .args(["--month", "mar"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout.matches("03/17 - St. Patrick's Day").count(),
+        2,
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_invalid_years() {
+    for (years, error) in [
+        (
+            "2026-2024",
+            "Error: Invalid years: '2026-2024' ends before it starts\n",
+        ),
+        (
+            "2024",
+            "Error: Invalid years: '2024' (expected e.g. 2024-2026)\n",
+        ),
+    ] {
+        let output = calendar_cli().args(["--years", years]).output().unwrap();
+        assert!(!output.status.success());
+        assert_eq!(String::from_utf8(output.stderr).unwrap(), error);
+    }
+
+    let output = calendar_cli()
+        .args(["--years", "2024-2025", "--json"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_output_file_colors() {
+    let dir = std::env::temp_dir().join(format!("compact-calendar-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("calendar.txt");
+    let write = |args: &[&str], env: &[(&str, &str)]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+            .env_remove("NO_COLOR")
+            .env_remove("FORCE_COLOR")
+            .envs(env.iter().copied())
+            .args(["--config", "tests/fixtures/simple.toml", "--year", "2024"])
+            .args(["--month", "mar", "--bg", "dark", "--output"])
+            .arg(&path)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        std::fs::read_to_string(&path).unwrap().contains("\x1b[")
+    };
+
+    assert!(write(&[], &[]));
+    assert!(!write(&[], &[("NO_COLOR", "1")]));
+    assert!(!write(&["--color", "never"], &[]));
+    assert!(write(&["--color", "always"], &[("NO_COLOR", "1")]));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_list_colors() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+        .env("NO_COLOR", "1")
+        .arg("--list-colors")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("orange\nyellow\n"), "{}", stdout);
+    assert!(stdout.ends_with("light_cyan\n"), "{}", stdout);
+}
+
+#[test]
+fn test_range_effect_flag() {
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_compact-calendar-cli"))
+            .args(["--config", "tests/fixtures/simple.toml", "--year", "2024"])
+            .args(["--month", "feb", "--color", "always", "--bg", "dark"])
+            .args(["--today", "2024-01-01"])
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    // 02/10 to 02/16 is a yellow range in the fixture
+    let plain = String::from_utf8(run(&[]).stdout).unwrap();
+    let underlined = String::from_utf8(run(&["--range-effect"]).stdout).unwrap();
+    let bold = String::from_utf8(run(&["--range-effect", "bold"]).stdout).unwrap();
+    assert!(!plain.contains("\x1b[4m\x1b[30m"), "{}", plain);
+    assert!(underlined.contains("\x1b[4m\x1b[30m"), "{}", underlined);
+    assert!(bold.contains("\x1b[1m\x1b[30m"), "{}", bold);
+
+    let output = run(&["--range-effect", "blink"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("Error: Invalid effect: 'blink'"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_import_csv() {
+    let output = calendar_cli_without_month()
+        .args(["--year", "2024", "--month", "3"])
+        .args(["--import-csv", "tests/fixtures/events.csv"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("03/05 - Dentist, bring forms"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("03/09 to 03/12 - Trip"), "{}", stdout);
+}
+
+#[test]
+fn test_import_csv_reports_the_line() {
+    let output = run_with_stdin(
+        &[
+            "--config",
+            "tests/fixtures/empty.toml",
+            "--import-csv",
+            "/dev/stdin",
+        ],
+        "2024-03-05;Semicolons\n",
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Line 1: Invalid date: '2024-03-05;Semicolons'"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_invalid_config_format() {
+    let output = calendar_cli_without_month()
+        .args(["--config-format", "json"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Invalid config format: 'json'. Use one of: toml, yaml"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_mini_layout_needs_a_single_month() {
+    let output = calendar_cli_without_month()
+        .args(["--layout", "mini"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("--layout mini shows a single month, e.g. --month march"),
+        "{}",
+        stderr
+    );
+
+    let output = calendar_cli_without_month()
+        .args(["--year", "2025", "--month", "feb", "--layout", "mini"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with("   February 2025\nMo Tu We"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_overview_flag() {
+    let output = calendar_cli_without_month()
+        .args(["--year", "2025", "--overview"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("      January                February"),
+        "{}",
+        stdout
+    );
+
+    let output = calendar_cli_without_month()
+        .args(["--year", "2025", "--overview", "--json"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("--overview can only be shown as text on the terminal"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_count_summary_after_the_calendar() {
+    let output = calendar_cli_without_month()
+        .args(["--year", "2025", "--month", "feb", "--count"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout
+            .ends_with("\n2025: 20 working days, 8 weekend days, 0 events, 0 ranges (0 days)\n\n"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_invalid_weekday_highlight() {
+    let output = calendar_cli_without_month()
+        .args(["--highlight-weekday", "wed"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Invalid weekday highlight: 'wed' (expected DAY:COLOR, e.g. wed:blue)"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_today_flag_pins_today_and_past_styling() {
+    let week_three = |today: &str| {
+        let output = calendar_cli_without_month()
+            .args(["--year", "2026", "--month", "1", "--color", "always"])
+            .args(["--today", today])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .find(|line| line.starts_with("│W03"))
+            .unwrap()
+            .to_string()
+    };
+
+    let week = week_three("2026-01-14");
+    assert!(week.contains("\x1b[9m13\x1b[0m"), "{}", week);
+    assert!(week.contains("\x1b[4m14\x1b[0m"), "{}", week);
+    assert!(week.contains("   15   16 "), "{}", week);
+
+    // A day later, the underline moves on and the 14th is struck through
+    let week = week_three("2026-01-15");
+    assert!(week.contains("\x1b[9m14\x1b[0m"), "{}", week);
+    assert!(week.contains("\x1b[4m15\x1b[0m"), "{}", week);
+    assert!(week.contains("   16 "), "{}", week);
+}
@@ -0,0 +1,17 @@
+use chrono::NaiveDate;
+use compact_calendar_cli::models::{
+    CalendarOptions, MonthFilter, PastDateDisplay, WeekNumbering, WeekendDisplay,
+};
+
+/// Options shared by the rendering tests: a fixed `today` of 2026-01-15,
+/// sequential week numbers and no weekend or past-date styling.
+pub fn options(month_filter: MonthFilter) -> CalendarOptions {
+    CalendarOptions {
+        weekend_display: WeekendDisplay::Normal,
+        past_date_display: PastDateDisplay::Normal,
+        month_filter,
+        today: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+        week_numbering: WeekNumbering::Sequential,
+        ..CalendarOptions::default()
+    }
+}
@@ -0,0 +1,95 @@
+use chrono::NaiveDate;
+use compact_calendar_cli::csv::{parse_csv, CsvEvent};
+use compact_calendar_cli::models::MonthFilter;
+
+mod common;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_csv_rows_become_details_and_ranges() {
+    let events = parse_csv(
+        "2024-03-05,\"Dentist, bring \"\"forms\"\"\",red\n\
+         \n\
+         # Trips\n\
+         2024-03-09,Trip,,2024-03-12\n\
+         12-25,Christmas\n",
+        ',',
+    )
+    .unwrap();
+    assert_eq!(
+        events[0],
+        CsvEvent {
+            date: "2024-03-05".to_string(),
+            description: "Dentist, bring \"forms\"".to_string(),
+            color: Some("red".to_string()),
+            end_date: None,
+        }
+    );
+    assert_eq!(events.len(), 3);
+
+    let mut config = compact_calendar_cli::parse_config(
+        r#"
+        [dates."2024-03-05"]
+        description = "From the config"
+        "#,
+    )
+    .unwrap();
+    config.import_csv_events(&events);
+    let calendar =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::All), config);
+
+    // The config wins over an import on the same date
+    assert_eq!(
+        calendar.details[&date(2024, 3, 5)][0].description,
+        "From the config"
+    );
+    assert_eq!(
+        calendar.details[&date(2024, 12, 25)][0].description,
+        "Christmas"
+    );
+    assert_eq!(calendar.ranges.len(), 1);
+    assert_eq!(
+        (calendar.ranges[0].start, calendar.ranges[0].end),
+        (date(2024, 3, 9), date(2024, 3, 12))
+    );
+    assert_eq!(calendar.ranges[0].color, "blue");
+}
+
+#[test]
+fn test_csv_header_and_delimiter() {
+    let events = parse_csv(
+        "Description;Date;End Date\nLaunch week;2024-06-03;2024-06-07\n",
+        ';',
+    )
+    .unwrap();
+    assert_eq!(
+        events,
+        vec![CsvEvent {
+            date: "2024-06-03".to_string(),
+            description: "Launch week".to_string(),
+            color: None,
+            end_date: Some("2024-06-07".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn test_csv_errors_name_the_line() {
+    let err = parse_csv("date,description\n2024-03-05,Ok\n2024-13-01,Bad\n", ',').unwrap_err();
+    assert_eq!(err, "Line 3: Invalid date: '2024-13-01'");
+
+    let err = parse_csv("2024-03-12,Trip,,2024-03-09\n", ',').unwrap_err();
+    assert_eq!(err, "Line 1: end_date 2024-03-09 is before date 2024-03-12");
+
+    let err = parse_csv("2024-03-05,\"Unterminated\n", ',').unwrap_err();
+    assert_eq!(err, "Line 1: unterminated quoted field");
+
+    let err = parse_csv("date,title\n", ',').unwrap_err();
+    assert_eq!(
+        err,
+        "Line 1: Unknown column: 'title'. Use date, description, color and end_date"
+    );
+}
@@ -0,0 +1,36 @@
+use compact_calendar_cli::diff::diff_calendars;
+use compact_calendar_cli::models::MonthFilter;
+use std::path::PathBuf;
+
+mod common;
+
+#[test]
+fn test_diff_recolor_and_added_event() {
+    let old =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/diff_old.toml")).unwrap();
+    let new =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/diff_new.toml")).unwrap();
+    let old = compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::All), old);
+    let new = compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::All), new);
+
+    let lines = diff_calendars(&old, &new);
+    assert_eq!(
+        lines,
+        vec![
+            "2024-03-15: color red→green".to_string(),
+            "2024-05-01: color none→purple, added \"Launch\"".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_diff_identical_configs() {
+    let config =
+        || compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let old =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::All), config());
+    let new =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::All), config());
+
+    assert!(diff_calendars(&old, &new).is_empty());
+}
@@ -0,0 +1,78 @@
+use compact_calendar_cli::formatting::display_width;
+use compact_calendar_cli::models::{BorderStyle, CalendarOptions, MonthFilter};
+use compact_calendar_cli::rendering::CalendarRenderer;
+use std::path::PathBuf;
+
+mod common;
+
+#[test]
+fn test_framed_output() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let options = CalendarOptions {
+        framed: true,
+        ..common::options(MonthFilter::Single(4))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    let lines: Vec<&str> = output.trim_end().lines().collect();
+    let first = lines[0];
+    let last = lines[lines.len() - 1];
+    assert!(
+        first.starts_with("╔═ COMPACT CALENDAR 2024 ═"),
+        "{}",
+        output
+    );
+    assert!(first.ends_with("═╗"));
+    assert!(last.starts_with("╚═") && last.ends_with("═╝"));
+    assert!(last.chars().skip(1).take_while(|c| *c == '═').count() > 40);
+
+    let width = display_width(first);
+    assert!(lines.iter().all(|line| display_width(line) == width));
+
+    assert_eq!(
+        lines[lines.len() - 2].trim_matches(|c| c == '║' || c == ' '),
+        "1 event · 1 range · 22 workdays"
+    );
+}
+
+#[test]
+fn test_border_style_leaves_titles_alone() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let render = |border_style| {
+        let options = CalendarOptions {
+            framed: true,
+            border_style,
+            title: Some("A│B─C".to_string()),
+            subtitle: Some("┌ on call ┘".to_string()),
+            ..common::options(MonthFilter::Single(4))
+        };
+        let calendar = compact_calendar_cli::build_calendar(2024, options, config.clone());
+        CalendarRenderer::new(&calendar).render_to_string()
+    };
+
+    let ascii = render(BorderStyle::Ascii);
+    let lines: Vec<&str> = ascii.trim_end().lines().collect();
+    assert!(lines[0].starts_with("+= A│B─C ="), "{}", ascii);
+    assert_eq!(
+        lines[2].split_whitespace().nth(2),
+        Some("A│B─C"),
+        "{}",
+        ascii
+    );
+    assert_eq!(
+        lines[3].trim_matches(|c| c == '|' || c == ' '),
+        "┌ on call ┘"
+    );
+    assert!(lines[lines.len() - 3].starts_with("+---"), "{}", ascii);
+
+    // The double divider meets the double sides in a tee
+    let double = render(BorderStyle::Double);
+    let lines: Vec<&str> = double.trim_end().lines().collect();
+    let divider = lines[lines.len() - 3];
+    assert!(divider.starts_with("╠═══"), "{}", double);
+    assert!(divider.ends_with("═══╣"), "{}", double);
+    assert!(double.contains("┌ on call ┘"), "{}", double);
+}
@@ -0,0 +1,149 @@
+use chrono::NaiveDate;
+use compact_calendar_cli::locale::{Locale, MAX_MONTH_NAME_WIDTH};
+use compact_calendar_cli::models::{CalendarOptions, MonthFilter, WeekStart};
+use compact_calendar_cli::rendering::CalendarRenderer;
+use std::path::PathBuf;
+
+mod common;
+
+fn render_header(subtitle: Option<&str>) -> Vec<String> {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/subtitle.toml")).unwrap();
+    let options = CalendarOptions {
+        subtitle: subtitle.map(str::to_string),
+        ..common::options(MonthFilter::Single(7))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    output.lines().take(4).map(str::to_string).collect()
+}
+
+#[test]
+fn test_subtitle_from_config() {
+    let header = render_header(None);
+    assert_eq!(header[0], format!("┌{}┐", "─".repeat(48)));
+    assert_eq!(header[1], format!("│{:^48}│", "COMPACT CALENDAR 2024"));
+    assert_eq!(header[2], format!("│{:^48}│", "Q3 Planning"));
+    assert_eq!(header[3], format!("├{}┤", "─".repeat(48)));
+}
+
+#[test]
+fn test_subtitle_flag_overrides_config() {
+    let header = render_header(Some("Été – Sommer"));
+    assert_eq!(header[2], format!("│{:^48}│", "Été – Sommer"));
+}
+
+#[test]
+fn test_long_subtitle_is_cut_to_the_box() {
+    let long = "Quarterly planning, offsites and the all-hands schedule";
+    let header = render_header(Some(long));
+    assert_eq!(
+        header[2],
+        "│Quarterly planning, offsites and the all-hands …│"
+    );
+
+    let header = render_header(Some(&"Été – Sommer · ".repeat(5)));
+    assert_eq!(header[2].chars().count(), 50, "{}", header[2]);
+    assert!(header[2].ends_with("…│"), "{}", header[2]);
+    assert!(
+        header[2].starts_with("│Été – Sommer · Été"),
+        "{}",
+        header[2]
+    );
+}
+
+#[test]
+fn test_empty_subtitle_keeps_single_line_header() {
+    let header = render_header(Some(""));
+    assert_eq!(header[1], format!("│{:^48}│", "COMPACT CALENDAR 2024"));
+    assert_eq!(header[2], format!("├{}┤", "─".repeat(48)));
+}
+
+#[test]
+fn test_locale_names_fit_the_columns() {
+    for name in ["en", "de", "fr", "es", "it"] {
+        let locale = Locale::from_name(name).unwrap();
+        for month in 1..=12 {
+            let month_name = locale.month_name(month);
+            assert!(
+                month_name.chars().count() <= MAX_MONTH_NAME_WIDTH,
+                "{}",
+                month_name
+            );
+        }
+        for day in WeekStart::Monday.days() {
+            assert_eq!(locale.weekday(day).chars().count(), 3);
+        }
+    }
+    assert_eq!(Locale::from_name("de_DE.UTF-8"), Ok(Locale::German));
+    assert!(Locale::from_name("xx").is_err());
+}
+
+#[test]
+fn test_localized_header_and_month_labels() {
+    let options = CalendarOptions {
+        month_filter: MonthFilter::Single(3),
+        today: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+        locale: Locale::German,
+        ..CalendarOptions::default()
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, Default::default());
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    assert!(
+        output.contains("│              Mo.  Di.  Mi.  Do.  Fr.  Sa.  So. │"),
+        "{}",
+        output
+    );
+    assert!(output.contains(" März     │"), "{}", output);
+}
+
+fn render_title(settings: &str, title: Option<&str>) -> String {
+    let config = compact_calendar_cli::parse_config(settings).unwrap();
+    let options = CalendarOptions {
+        month_filter: MonthFilter::Single(3),
+        today: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+        title: title.map(str::to_string),
+        ..CalendarOptions::default()
+    };
+    let calendar = compact_calendar_cli::build_calendar(2025, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    output.lines().nth(1).unwrap().to_string()
+}
+
+#[test]
+fn test_title_replaces_the_header() {
+    let config = "[settings]\ntitle = \"Platform {year}\"";
+    assert_eq!(
+        render_title(config, None),
+        format!("│{:^48}│", "Platform 2025")
+    );
+    // The flag wins over the config
+    assert_eq!(
+        render_title(config, Some("Q2 PLANNING {year}")),
+        format!("│{:^48}│", "Q2 PLANNING 2025")
+    );
+
+    let long = "Quarterly planning for the platform infrastructure group {year}";
+    assert_eq!(
+        render_title("", Some(long)),
+        "│Quarterly planning for the platform infrastruct…│"
+    );
+}
+
+#[test]
+fn test_year_progress_at_mid_year() {
+    let config = compact_calendar_cli::parse_config("").unwrap();
+    let options = CalendarOptions {
+        today: NaiveDate::from_ymd_opt(2024, 7, 2).unwrap(),
+        year_progress: true,
+        ..common::options(MonthFilter::Single(7))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    let line = output
+        .lines()
+        .find(|line| line.ends_with("% of 2024"))
+        .unwrap();
+    assert_eq!(line, "[██████████░░░░░░░░░░] 50% of 2024");
+}
@@ -0,0 +1,119 @@
+use chrono::{NaiveDate, Weekday};
+use compact_calendar_cli::holidays::{easter_sunday, last_weekday, nth_weekday, HolidayPreset};
+use compact_calendar_cli::models::{CalendarOptions, MonthFilter};
+use compact_calendar_cli::rendering::CalendarRenderer;
+use std::path::PathBuf;
+
+mod common;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_holiday_provider() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/empty.toml")).unwrap();
+    let options = common::options(MonthFilter::Single(3));
+    let mut calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let good_friday = NaiveDate::from_ymd_opt(2024, 3, 29).unwrap();
+    calendar.set_holiday_provider(Box::new(move |year| {
+        assert_eq!(year, 2024);
+        vec![(good_friday, "Good Friday".to_string())]
+    }));
+
+    assert!(calendar.is_holiday(good_friday));
+    assert!(!calendar.is_business_day(good_friday));
+    assert!(calendar.is_business_day(NaiveDate::from_ymd_opt(2024, 3, 28).unwrap()));
+    // March 2024 has 21 weekdays
+    assert_eq!(calendar.stats().workdays, 20);
+
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(output.contains("03/29 - Good Friday"), "{}", output);
+}
+
+#[test]
+fn test_movable_weekdays() {
+    assert_eq!(nth_weekday(2024, 11, Weekday::Thu, 4), date(2024, 11, 28));
+    assert_eq!(nth_weekday(2025, 9, Weekday::Mon, 1), date(2025, 9, 1));
+    assert_eq!(last_weekday(2024, 5, Weekday::Mon), date(2024, 5, 27));
+    assert_eq!(last_weekday(2025, 5, Weekday::Mon), date(2025, 5, 26));
+    // The month ends on the weekday itself
+    assert_eq!(last_weekday(2024, 8, Weekday::Sat), date(2024, 8, 31));
+    assert_eq!(last_weekday(2024, 12, Weekday::Tue), date(2024, 12, 31));
+}
+
+#[test]
+fn test_easter_sunday() {
+    assert_eq!(easter_sunday(2024), date(2024, 3, 31));
+    assert_eq!(easter_sunday(2025), date(2025, 4, 20));
+    assert_eq!(easter_sunday(2019), date(2019, 4, 21));
+    assert_eq!(easter_sunday(2038), date(2038, 4, 25));
+}
+
+#[test]
+fn test_us_holidays() {
+    let holidays = HolidayPreset::Us.holidays(2024);
+    let on = |name: &str| holidays.iter().find(|(_, n)| n == name).unwrap().0;
+
+    assert_eq!(on("Martin Luther King Jr. Day"), date(2024, 1, 15));
+    assert_eq!(on("Memorial Day"), date(2024, 5, 27));
+    assert_eq!(on("Labor Day"), date(2024, 9, 2));
+    assert_eq!(on("Thanksgiving Day"), date(2024, 11, 28));
+    assert_eq!(holidays.len(), 11);
+    assert!(holidays.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+    // Juneteenth was first observed federally in 2021
+    assert_eq!(HolidayPreset::Us.holidays(2020).len(), 10);
+}
+
+#[test]
+fn test_uk_and_de_holidays() {
+    let uk = HolidayPreset::Uk.holidays(2024);
+    assert!(uk.contains(&(date(2024, 3, 29), "Good Friday".to_string())));
+    assert!(uk.contains(&(date(2024, 4, 1), "Easter Monday".to_string())));
+    assert!(uk.contains(&(date(2024, 8, 26), "Summer Bank Holiday".to_string())));
+
+    let de = HolidayPreset::De.holidays(2024);
+    assert!(de.contains(&(date(2024, 5, 9), "Ascension Day".to_string())));
+    assert!(de.contains(&(date(2024, 5, 20), "Whit Monday".to_string())));
+}
+
+#[test]
+fn test_holiday_preset_from_name() {
+    assert_eq!(HolidayPreset::from_name("US"), Ok(HolidayPreset::Us));
+    assert_eq!(HolidayPreset::from_name("gb"), Ok(HolidayPreset::Uk));
+    assert_eq!(
+        HolidayPreset::from_name("fr"),
+        Err("Invalid holidays: 'fr'. Use one of: us, uk, de".to_string())
+    );
+}
+
+#[test]
+fn test_holiday_preset_merges_under_config() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+[dates.2024-11-28]
+description = "Family dinner"
+color = "orange"
+"#,
+    )
+    .unwrap();
+    let options = CalendarOptions {
+        month_filter: MonthFilter::Single(11),
+        holiday_preset: Some(HolidayPreset::Us),
+        ..common::options(MonthFilter::Single(3))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+
+    let thanksgiving = date(2024, 11, 28);
+    assert!(calendar.is_holiday(thanksgiving));
+    assert_eq!(
+        calendar.details[&thanksgiving][0].description,
+        "Family dinner"
+    );
+    assert_eq!(
+        calendar.details[&date(2024, 11, 11)][0].description,
+        "Veterans Day"
+    );
+}
@@ -0,0 +1,205 @@
+use chrono::NaiveDate;
+use compact_calendar_cli::ics::{parse_ics, IcsEvent};
+use compact_calendar_cli::models::MonthFilter;
+use compact_calendar_cli::output::render_ics;
+use compact_calendar_cli::rendering::CalendarRenderer;
+
+mod common;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_ics_round_trip() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+        [dates."2024-03-05"]
+        description = "Dentist; bring forms, please"
+
+        [[ranges]]
+        start = "2024-03-09"
+        end = "2024-03-12"
+        color = "blue"
+        description = "Trip"
+        "#,
+    )
+    .unwrap();
+    let exported =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::All), config);
+    let events = parse_ics(&render_ics(&exported)).unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].summary, "Dentist; bring forms, please");
+    assert_eq!(
+        (events[0].start, events[0].end),
+        (date(2024, 3, 5), date(2024, 3, 5))
+    );
+    assert_eq!(events[1].summary, "Trip");
+    assert_eq!(
+        (events[1].start, events[1].end),
+        (date(2024, 3, 9), date(2024, 3, 12))
+    );
+
+    let mut config = compact_calendar_cli::parse_config("").unwrap();
+    config.import_events(&events, 2024);
+    let imported =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::All), config);
+    assert_eq!(
+        imported.details[&date(2024, 3, 5)][0].description,
+        "Dentist; bring forms, please"
+    );
+    assert_eq!(imported.ranges.len(), 1);
+    assert_eq!(imported.ranges[0].end, date(2024, 3, 12));
+}
+
+#[test]
+fn test_import_filters_to_year() {
+    let events = parse_ics(
+        "BEGIN:VCALENDAR\r\n\
+         BEGIN:VEVENT\r\n\
+         UID:a\r\n\
+         SUMMARY:Last year\r\n\
+         DTSTART;VALUE=DATE:20230601\r\n\
+         END:VEVENT\r\n\
+         BEGIN:VEVENT\r\n\
+         UID:b\r\n\
+         SUMMARY:Timed\r\n\
+         DTSTART:20240601T090000Z\r\n\
+         DTEND:20240601T100000Z\r\n\
+         END:VEVENT\r\n\
+         END:VCALENDAR\r\n",
+    )
+    .unwrap();
+    assert_eq!(
+        events[1],
+        IcsEvent {
+            uid: Some("b".to_string()),
+            summary: "Timed".to_string(),
+            start: date(2024, 6, 1),
+            end: date(2024, 6, 1),
+        }
+    );
+
+    let mut config = compact_calendar_cli::parse_config("").unwrap();
+    config.import_events(&events, 2024);
+    assert_eq!(config.dates.len(), 1);
+    assert!(config.dates.contains_key("2024-06-01"));
+}
+
+#[test]
+fn test_ics_errors_name_the_event() {
+    let err = parse_ics(
+        "BEGIN:VEVENT\n\
+         UID:launch-1\n\
+         DTSTART;VALUE=DATE:2024-06-01\n\
+         END:VEVENT\n",
+    )
+    .unwrap_err();
+    assert_eq!(err, "Line 3: Event launch-1: invalid DTSTART: '2024-06-01'");
+
+    let err = parse_ics("BEGIN:VEVENT\nSUMMARY:No start\nEND:VEVENT\n").unwrap_err();
+    assert_eq!(err, "Event on line 1: missing DTSTART");
+}
+
+#[test]
+fn test_exclusive_end_on_month_boundary() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+        [[ranges]]
+        start = "2024-03-25"
+        end = "2024-04-01"
+        end_inclusive = false
+        color = "blue"
+        description = "Sprint"
+
+        [[ranges]]
+        start = "02-01"
+        end = "03-01"
+        end_inclusive = false
+        color = "green"
+        description = "February"
+        "#,
+    )
+    .unwrap();
+    let calendar = compact_calendar_cli::build_calendar(
+        2024,
+        common::options(MonthFilter::All),
+        config.clone(),
+    );
+
+    // The sprint stops at the end of March
+    assert_eq!(calendar.color_for(date(2024, 3, 31)), Some("blue"));
+    assert_eq!(calendar.color_for(date(2024, 4, 1)), None);
+    // A yearly range ending on March 1st covers the leap day only in leap years
+    assert_eq!(calendar.color_for(date(2024, 2, 29)), Some("green"));
+    assert_eq!(calendar.color_for(date(2024, 3, 1)), None);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(output.contains("03/25 to 03/31 - Sprint"), "{}", output);
+    assert!(output.contains("02/01 to 02/29 - February"), "{}", output);
+    let next_year =
+        compact_calendar_cli::build_calendar(2025, common::options(MonthFilter::All), config);
+    let february = next_year
+        .ranges
+        .iter()
+        .find(|range| range.description.as_deref() == Some("February"))
+        .unwrap();
+    assert_eq!(february.end, date(2025, 2, 28));
+
+    // Exported with the exclusive end it was written with, and read back
+    let exported = render_ics(&calendar);
+    assert!(exported.contains("DTSTART;VALUE=DATE:20240325\r\nDTEND;VALUE=DATE:20240401\r\n"));
+    let mut config = compact_calendar_cli::parse_config("").unwrap();
+    config.import_events(&parse_ics(&exported).unwrap(), 2024);
+    let imported =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::All), config);
+    let bounds: Vec<_> = imported.ranges.iter().map(|r| (r.start, r.end)).collect();
+    assert_eq!(
+        bounds,
+        [
+            (date(2024, 2, 1), date(2024, 2, 29)),
+            (date(2024, 3, 25), date(2024, 3, 31))
+        ]
+    );
+}
+
+#[test]
+fn test_exclusive_end_needs_a_day() {
+    let err = compact_calendar_cli::parse_config(
+        r#"
+        [[ranges]]
+        start = "2024-03-01"
+        end = "2024-03-01"
+        end_inclusive = false
+        color = "blue"
+        "#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        "Invalid config: Range 1: exclusive end 2024-03-01 leaves no days after start 2024-03-01"
+    );
+}
+
+#[test]
+fn test_alarm_properties_stay_with_the_alarm() {
+    let contents = std::fs::read_to_string("tests/fixtures/alarm.ics").unwrap();
+    let events = parse_ics(&contents).unwrap();
+    assert_eq!(
+        events,
+        [IcsEvent {
+            uid: Some("launch".to_string()),
+            summary: "Launch".to_string(),
+            start: date(2024, 3, 10),
+            end: date(2024, 3, 10),
+        }]
+    );
+
+    let mut config = compact_calendar_cli::parse_config("").unwrap();
+    config.import_events(&events, 2024);
+    let calendar =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::Single(3)), config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(output.contains("03/10 - Launch"), "{}", output);
+    assert!(!output.contains("Reminder"), "{}", output);
+}
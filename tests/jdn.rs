@@ -0,0 +1,9 @@
+use chrono::NaiveDate;
+use compact_calendar_cli::formatting::jdn;
+
+#[test]
+fn test_jdn_reference_values() {
+    assert_eq!(jdn(NaiveDate::from_ymd_opt(2000, 1, 1).unwrap()), 2_451_545);
+    assert_eq!(jdn(NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()), 2_440_588);
+    assert_eq!(jdn(NaiveDate::from_ymd_opt(2024, 3, 4).unwrap()), 2_460_374);
+}
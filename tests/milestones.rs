@@ -0,0 +1,58 @@
+use chrono::NaiveDate;
+use compact_calendar_cli::models::{CalendarOptions, DayRender, MonthFilter};
+use compact_calendar_cli::rendering::CalendarRenderer;
+use std::path::PathBuf;
+
+mod common;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn build_calendar(today: NaiveDate) -> compact_calendar_cli::models::Calendar {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/milestones.toml"))
+            .unwrap();
+    let options = CalendarOptions {
+        today,
+        ..common::options(MonthFilter::DateRange(date(2024, 4, 1), date(2024, 5, 31)))
+    };
+    compact_calendar_cli::build_calendar(2024, options, config)
+}
+
+#[test]
+fn test_milestone_marker_and_countdown() {
+    let calendar = build_calendar(date(2024, 4, 17));
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    assert!(output.contains("▶01"), "{}", output);
+    assert!(output.contains("▶10"), "{}", output);
+    assert!(output.contains("05/01 - 14 days to Launch"), "{}", output);
+    assert!(
+        output.contains("04/10 - Kickoff done (7 days ago)"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_milestone_highlight_color() {
+    let calendar = build_calendar(date(2024, 4, 17));
+
+    assert_eq!(
+        calendar.resolve_day(date(2024, 5, 1)),
+        DayRender {
+            date: date(2024, 5, 1),
+            color: Some("orange".to_string()),
+            events: vec!["Launch".to_string()],
+        }
+    );
+}
+
+#[test]
+fn test_milestone_today() {
+    let calendar = build_calendar(date(2024, 5, 1));
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    assert!(output.contains("05/01 - Launch today"), "{}", output);
+}
@@ -0,0 +1,105 @@
+use compact_calendar_cli::models::MonthFilter;
+use compact_calendar_cli::month_grid::{mini_month, render_year_grid, zip_columns};
+use compact_calendar_cli::rendering::{CalendarRenderer, ColorChoice};
+use std::path::PathBuf;
+
+mod common;
+
+fn build_calendar(year: i32) -> compact_calendar_cli::models::Calendar {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let options = common::options(MonthFilter::All);
+    compact_calendar_cli::build_calendar(year, options, config)
+}
+
+#[test]
+fn test_year_grid_side_by_side() {
+    let output = render_year_grid(
+        &[build_calendar(2024), build_calendar(2025)],
+        ColorChoice::Never,
+    );
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines[0], "    January 2024           January 2025");
+    assert_eq!(lines[1], "Mo Tu We Th Fr Sa Su   Mo Tu We Th Fr Sa Su");
+    // 2024 starts on a Monday, 2025 on a Wednesday
+    assert_eq!(lines[2], " 1  2  3  4  5  6  7          1  2  3  4  5");
+    assert!(output.contains("December 2024"));
+    assert!(output.contains("December 2025"));
+}
+
+#[test]
+fn test_mini_month_has_fixed_height() {
+    std::env::set_var("NO_COLOR", "1");
+    let february = mini_month(&build_calendar(2021), 2);
+    assert_eq!(february.len(), 8);
+    assert_eq!(february[5], "22 23 24 25 26 27 28");
+    assert_eq!(february[6], "");
+}
+
+#[test]
+fn test_zip_columns_pads_to_widest_line() {
+    let left = vec!["a".to_string(), "abc".to_string()];
+    let right = vec!["x".to_string()];
+    assert_eq!(zip_columns(&[left, right], 2), vec!["a    x", "abc"]);
+}
+
+#[test]
+fn test_mini_layout_lists_annotations_below() {
+    let mut calendar = build_calendar(2025);
+    calendar.month_filter = compact_calendar_cli::models::MonthFilter::Single(2);
+    let output = CalendarRenderer::new(&calendar).render_mini_to_string(2);
+
+    assert_eq!(
+        output,
+        "   February 2025
+Mo Tu We Th Fr Sa Su
+                1  2
+ 3  4  5  6  7  8  9
+10 11 12 13 14 15 16
+17 18 19 20 21 22 23
+24 25 26 27 28
+
+02/01 - Q1 Review Due
+02/10 to 02/16 - Sprint Planning
+02/14 - Valentine's Day
+
+"
+    );
+}
+
+#[test]
+fn test_overview_puts_three_months_in_a_row() {
+    let calendar = build_calendar(2025);
+    let output = CalendarRenderer::new(&calendar).render_overview_to_string();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines[0].trim(), "2025");
+    assert_eq!(
+        lines[2],
+        "      January                February                March"
+    );
+    assert_eq!(
+        lines[3],
+        "Mo Tu We Th Fr Sa Su   Mo Tu We Th Fr Sa Su   Mo Tu We Th Fr Sa Su"
+    );
+    // Each row keeps room for six weeks, then a blank line
+    assert_eq!(lines[10], "");
+    assert!(lines[11].contains("April"));
+    assert!(lines[29].contains("December"));
+    // No annotation column
+    assert!(!output.contains("Sprint Planning"));
+    assert!(output.ends_with("29 30 31\n\n"), "{:?}", output);
+}
+
+#[test]
+fn test_overview_follows_the_month_filter() {
+    let mut calendar = build_calendar(2025);
+    calendar.month_filter = MonthFilter::Quarter(2);
+    let output = CalendarRenderer::new(&calendar).render_overview_to_string();
+
+    assert!(output.contains("April"), "{}", output);
+    assert!(output.contains("June"), "{}", output);
+    assert!(!output.contains("March"), "{}", output);
+    assert!(!output.contains("July"), "{}", output);
+}
@@ -0,0 +1,340 @@
+use chrono::NaiveDate;
+use compact_calendar_cli::models::{CalendarBuilder, CalendarOptions, MonthFilter};
+use compact_calendar_cli::output::{
+    render_ics, render_json, render_to, render_tsv, write_output, OutputFormat,
+};
+use compact_calendar_cli::rendering::{CalendarRenderer, ColorPalette};
+use std::path::{Path, PathBuf};
+
+mod common;
+
+fn build_calendar(
+    config_path: &str,
+    month_filter: MonthFilter,
+) -> compact_calendar_cli::models::Calendar {
+    let config = compact_calendar_cli::load_config(&PathBuf::from(config_path)).unwrap();
+    let options = common::options(month_filter);
+    compact_calendar_cli::build_calendar(2024, options, config)
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("compact-calendar-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_output_json_inferred_from_extension() {
+    let calendar = build_calendar("tests/fixtures/simple.toml", MonthFilter::Single(3));
+    let path = temp_dir("json").join("calendar.json");
+
+    let format = OutputFormat::from_path(&path).unwrap();
+    assert_eq!(format, OutputFormat::Json);
+    write_output(&calendar, format, false, &path).unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert_eq!(json["year"], 2024);
+    assert_eq!(json["days"].as_array().unwrap().len(), 31);
+    assert_eq!(json["days"][16]["date"], "2024-03-17");
+    assert_eq!(json["days"][16]["color"], "green");
+    assert_eq!(json["days"][16]["events"][0], "St. Patrick's Day");
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_output_text_leaves_no_temp_file() {
+    let calendar = build_calendar("tests/fixtures/empty.toml", MonthFilter::Single(3));
+    let dir = temp_dir("text");
+    let path = dir.join("calendar.txt");
+
+    write_output(&calendar, OutputFormat::Text, false, &path).unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("COMPACT CALENDAR 2024"));
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_output_text_keeps_colors() {
+    let calendar = build_calendar("tests/fixtures/simple.toml", MonthFilter::Single(3));
+    let dir = temp_dir("colored");
+    let colored = dir.join("colored.txt");
+    let plain = dir.join("plain.txt");
+
+    write_output(&calendar, OutputFormat::Text, true, &colored).unwrap();
+    write_output(&calendar, OutputFormat::Text, false, &plain).unwrap();
+
+    let colored = std::fs::read_to_string(&colored).unwrap();
+    let plain = std::fs::read_to_string(&plain).unwrap();
+    assert!(colored.contains("\x1b["), "{}", colored);
+    assert!(!plain.contains("\x1b["), "{}", plain);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_output_missing_parent_directory() {
+    let calendar = build_calendar("tests/fixtures/empty.toml", MonthFilter::Single(3));
+    let path = Path::new("tests/does-not-exist/calendar.json");
+
+    let err = write_output(&calendar, OutputFormat::Json, false, path).unwrap_err();
+    assert!(err.contains("does not exist"), "{}", err);
+}
+
+#[test]
+fn test_output_svg_day_cells() {
+    let calendar = build_calendar("tests/fixtures/simple.toml", MonthFilter::Single(3));
+    let svg = CalendarRenderer::new(&calendar).render_to_svg();
+
+    assert!(svg.starts_with("<svg "));
+    assert_eq!(svg.matches("<rect ").count(), 31);
+
+    let green = ColorPalette::get_color_value("green").unwrap().normal;
+    let fill = format!("#{:02x}{:02x}{:02x}", green.0, green.1, green.2);
+    let st_patricks = svg
+        .lines()
+        .position(|line| line.contains(">17</text>"))
+        .unwrap();
+    let cell = svg.lines().nth(st_patricks - 1).unwrap();
+    assert!(cell.contains(&format!("fill=\"{}\"", fill)), "{}", cell);
+    assert!(svg.contains("03/17 - St. Patrick's Day"));
+}
+
+#[test]
+fn test_output_days_csv() {
+    let calendar = build_calendar("tests/fixtures/simple.toml", MonthFilter::All);
+    let mut output = Vec::new();
+    render_to(&calendar, OutputFormat::DaysCsv, false, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    let mut lines = output.lines();
+    assert_eq!(
+        lines.next().unwrap(),
+        "# ordinal,weekday_num,event_count,has_color"
+    );
+    let rows: Vec<Vec<u32>> = lines
+        .map(|line| line.split(',').map(|n| n.parse().unwrap()).collect())
+        .collect();
+    assert_eq!(rows.len(), 366);
+
+    // 03/15 is Project Alpha Deadline, a Friday
+    assert_eq!(rows[74], vec![75, 4, 1, 1]);
+    // 02/14 is Valentine's Day inside the Sprint Planning range
+    assert_eq!(rows[44], vec![45, 2, 2, 1]);
+    // 03/20 has nothing on it
+    assert_eq!(rows[79], vec![80, 2, 0, 0]);
+}
+
+/// A sink that rejects every write
+struct BrokenPipe;
+
+impl std::io::Write for BrokenPipe {
+    fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_render_to_writer_propagates_errors() {
+    let calendar = build_calendar("tests/fixtures/simple.toml", MonthFilter::Single(3));
+    let renderer = CalendarRenderer::new(&calendar);
+
+    let mut buffer = Vec::new();
+    renderer.render_to_writer(&mut buffer).unwrap();
+    let output = String::from_utf8(buffer).unwrap();
+    assert!(output.contains("COMPACT CALENDAR 2024"));
+    assert!(output.contains("St. Patrick's Day"));
+
+    let err = renderer.render_to_writer(&mut BrokenPipe).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn test_json_weeks_match_rendered_rows() {
+    let calendar = build_calendar("tests/fixtures/simple.toml", MonthFilter::Single(3));
+    let json: serde_json::Value = serde_json::from_str(&render_json(&calendar)).unwrap();
+
+    let weeks = json["weeks"].as_array().unwrap();
+    assert_eq!(weeks.len(), 5);
+    assert_eq!(weeks[0]["week"], 1);
+    assert_eq!(weeks[0]["dates"].as_array().unwrap().len(), 7);
+    // The first row starts on the Monday before March 1st, as drawn
+    assert_eq!(weeks[0]["dates"][0]["date"], "2024-02-26");
+
+    let st_patricks = &weeks[2]["dates"][6];
+    assert_eq!(st_patricks["date"], "2024-03-17");
+    assert_eq!(st_patricks["day"], 17);
+    assert_eq!(st_patricks["month"], 3);
+    assert_eq!(st_patricks["is_weekend"], true);
+    assert_eq!(st_patricks["is_past"], true);
+    assert_eq!(st_patricks["is_today"], false);
+    assert_eq!(st_patricks["color"], "green");
+    assert_eq!(st_patricks["description"], "St. Patrick's Day");
+    assert_eq!(weeks[2]["dates"][0]["description"], serde_json::Value::Null);
+}
+
+#[test]
+fn test_render_ics() {
+    let calendar = build_calendar("tests/fixtures/simple.toml", MonthFilter::All);
+    let ics = render_ics(&calendar);
+    let lines: Vec<&str> = ics.split("\r\n").collect();
+
+    assert_eq!(lines[0], "BEGIN:VCALENDAR");
+    assert!(lines.contains(&"VERSION:2.0"));
+    assert!(lines.iter().any(|line| line.starts_with("PRODID:")));
+    assert_eq!(lines[lines.len() - 2], "END:VCALENDAR");
+    assert_eq!(
+        ics.matches("BEGIN:VEVENT").count(),
+        calendar.details.len() + calendar.ranges.len()
+    );
+
+    // A single-day event ends the following day
+    let st_patricks = ics.find("SUMMARY:St. Patrick's Day").unwrap();
+    let event = &ics[ics[..st_patricks].rfind("BEGIN:VEVENT").unwrap()..st_patricks];
+    assert!(
+        event.contains("DTSTART;VALUE=DATE:20240317\r\n"),
+        "{}",
+        event
+    );
+    assert!(event.contains("DTEND;VALUE=DATE:20240318\r\n"), "{}", event);
+
+    // Ranges are one event with an exclusive end
+    let sprint = ics.find("SUMMARY:Sprint Planning").unwrap();
+    let event = &ics[ics[..sprint].rfind("BEGIN:VEVENT").unwrap()..sprint];
+    assert!(
+        event.contains("DTSTART;VALUE=DATE:20240210\r\n"),
+        "{}",
+        event
+    );
+    assert!(event.contains("DTEND;VALUE=DATE:20240217\r\n"), "{}", event);
+
+    // UIDs are stable across exports
+    assert_eq!(ics, render_ics(&calendar));
+    let uids: Vec<&&str> = lines.iter().filter(|l| l.starts_with("UID:")).collect();
+    let unique: std::collections::HashSet<_> = uids.iter().collect();
+    assert_eq!(uids.len(), unique.len());
+}
+
+#[test]
+fn test_render_markdown() {
+    let calendar = build_calendar("tests/fixtures/simple.toml", MonthFilter::Single(3));
+    let markdown = CalendarRenderer::new(&calendar).render_to_markdown();
+    insta::assert_snapshot!(markdown);
+
+    // The same weeks as the text grid, plus the header and divider rows
+    let table_rows = markdown
+        .lines()
+        .filter(|line| line.starts_with('|'))
+        .count();
+    assert_eq!(table_rows, calendar.weeks().count() + 2);
+    assert_eq!(
+        OutputFormat::from_path(Path::new("year.md")),
+        Some(OutputFormat::Markdown)
+    );
+}
+
+#[test]
+fn test_markdown_escapes_pipes() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+        [dates."2024-03-05"]
+        description = "Review a|b"
+        color = "red"
+        "#,
+    )
+    .unwrap();
+    let options = CalendarOptions {
+        month_filter: MonthFilter::Single(3),
+        today: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+        ..CalendarOptions::default()
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let markdown = CalendarRenderer::new(&calendar).render_to_markdown();
+
+    assert!(
+        markdown.contains("| W10 | 04 | **05** | 06 | 07 | 08 | 09 | 10 | 03/05 - Review a\\|b |"),
+        "{}",
+        markdown
+    );
+}
+
+#[test]
+fn test_render_html() {
+    let options = CalendarOptions {
+        month_filter: MonthFilter::Single(3),
+        today: NaiveDate::from_ymd_opt(2024, 3, 12).unwrap(),
+        ..CalendarOptions::default()
+    };
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let html = CalendarRenderer::new(&calendar).render_to_html();
+
+    // Colors come straight from the terminal palette
+    let red = ColorPalette::get_color_value("red").unwrap().normal;
+    let red = format!("#{:02x}{:02x}{:02x}", red.0, red.1, red.2);
+    assert!(
+        html.contains(&format!(
+            "<td style=\"background:{};color:black\">15</td>",
+            red
+        )),
+        "{}",
+        html
+    );
+    assert!(html.contains("<td style=\"text-decoration:underline\">12</td>"));
+    assert!(html.contains("<td style=\"text-decoration:line-through\">11</td>"));
+    assert!(html.contains("<td class=\"dimmed\">16</td>"));
+    assert!(html.contains(
+        "<td class=\"events\">03/15 - Project Alpha Deadline, 03/17 - St. Patrick's Day</td>"
+    ));
+    // One row per week, like the text grid
+    assert_eq!(html.matches("</td></tr>").count(), calendar.weeks().count());
+    assert_eq!(
+        OutputFormat::from_path(Path::new("calendar.html")),
+        Some(OutputFormat::Html)
+    );
+}
+
+#[test]
+fn test_output_tsv() {
+    let calendar = build_calendar("tests/fixtures/simple.toml", MonthFilter::Single(2));
+    let mut output = Vec::new();
+    render_to(&calendar, OutputFormat::Tsv, false, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "# date\tcolor\tdescription\twhen\tweekend");
+    assert_eq!(lines.len(), 1 + 29);
+    assert_eq!(lines[1], "2024-02-01\tyellow\tQ1 Review Due\tpast\t0");
+    // Uncolored days keep their empty fields
+    assert_eq!(lines[3], "2024-02-03\t\t\tpast\t1");
+    // Valentine's Day colors its day inside the Sprint Planning range
+    assert_eq!(
+        lines[14],
+        "2024-02-14\tred\tValentine's Day, Sprint Planning\tpast\t0"
+    );
+    assert!(lines.iter().all(|line| line.split('\t').count() == 5));
+
+    let calendar = CalendarBuilder::new()
+        .year(2026)
+        .today(NaiveDate::from_ymd_opt(2026, 1, 15).unwrap())
+        .month_filter(MonthFilter::Single(1))
+        .build();
+    let output = render_tsv(&calendar);
+    let when: Vec<&str> = output
+        .lines()
+        .skip(14)
+        .take(3)
+        .map(|line| line.split('\t').nth(3).unwrap())
+        .collect();
+    assert_eq!(when, ["past", "today", "upcoming"]);
+}
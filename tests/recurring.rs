@@ -0,0 +1,113 @@
+use chrono::NaiveDate;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+const CONFIG: &str = r#"
+[dates."1990-05-12"]
+description = "Sam's birthday"
+color = "purple"
+recurring = "yearly"
+
+[dates."2000-02-29"]
+description = "Leap day anniversary"
+recurring = "yearly"
+
+[dates."2025-05-12"]
+description = "Sam's birthday dinner"
+color = "orange"
+"#;
+
+#[test]
+fn test_yearly_recurrence_expands_into_each_year() {
+    let config = compact_calendar_cli::parse_config(CONFIG).unwrap();
+
+    let details = config.parse_dates_for_year(2024);
+    assert_eq!(details[&date(2024, 5, 12)][0].description, "Sam's birthday");
+    assert_eq!(
+        details[&date(2024, 5, 12)][0].color.as_deref(),
+        Some("purple")
+    );
+    assert_eq!(
+        details[&date(2024, 2, 29)][0].description,
+        "Leap day anniversary"
+    );
+}
+
+#[test]
+fn test_leap_day_recurrence_falls_back_to_feb_28() {
+    let config = compact_calendar_cli::parse_config(CONFIG).unwrap();
+
+    let details = config.parse_dates_for_year(2023);
+    assert_eq!(
+        details[&date(2023, 2, 28)][0].description,
+        "Leap day anniversary"
+    );
+}
+
+#[test]
+fn test_explicit_date_comes_before_recurrence() {
+    let config = compact_calendar_cli::parse_config(CONFIG).unwrap();
+
+    let details = config.parse_dates_for_year(2025);
+    let descriptions: Vec<&str> = details[&date(2025, 5, 12)]
+        .iter()
+        .map(|detail| detail.description.as_str())
+        .collect();
+    // Both are kept; the one written for the date colors it
+    assert_eq!(descriptions, ["Sam's birthday dinner", "Sam's birthday"]);
+}
+
+#[test]
+fn test_unknown_recurrence_is_rejected() {
+    let err = compact_calendar_cli::parse_config(
+        r#"
+        [dates."1990-05-12"]
+        description = "Birthday"
+        recurring = "weekly"
+        "#,
+    )
+    .unwrap_err();
+    assert!(err.starts_with("Failed to parse TOML config"), "{}", err);
+}
+
+#[test]
+fn test_explicit_date_wins_over_more_severe_recurrence() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+        [dates."1990-05-12"]
+        description = "Sam's birthday"
+        severity = 5
+        recurring = "yearly"
+
+        [dates."1995-05-12"]
+        description = "Wedding anniversary"
+        severity = 3
+        recurring = "yearly"
+
+        [dates."2025-05-12"]
+        description = "Dentist"
+        color = "blue"
+        "#,
+    )
+    .unwrap();
+
+    let details = config.parse_dates_for_year(2025);
+    let descriptions: Vec<&str> = details[&date(2025, 5, 12)]
+        .iter()
+        .map(|detail| detail.description.as_str())
+        .collect();
+    // The recurrences still sort by severity after it
+    assert_eq!(
+        descriptions,
+        ["Dentist", "Sam's birthday", "Wedding anniversary"]
+    );
+
+    let calendar = compact_calendar_cli::build_calendar(
+        2025,
+        compact_calendar_cli::models::CalendarOptions::default(),
+        config,
+    );
+    assert_eq!(calendar.color_for(date(2025, 5, 12)), Some("blue"));
+}
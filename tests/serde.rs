@@ -0,0 +1,81 @@
+#![cfg(feature = "serde")]
+
+use anstyle::Effects;
+use chrono::{NaiveDate, NaiveTime};
+use compact_calendar_cli::models::{
+    CalendarBuilder, DateDetail, DateRange, MonthFilter, WeekLabelFormat, WeekStart,
+};
+use compact_calendar_cli::rendering::ColorValue;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_models_read_the_toml_schema() {
+    let detail: DateDetail = toml::from_str(
+        r#"
+        description = "Standup"
+        time = "09:30"
+        effects = ["bold", "underline"]
+        "#,
+    )
+    .unwrap();
+    assert_eq!(detail.description, "Standup");
+    assert_eq!(detail.color, None);
+    assert_eq!(detail.time, NaiveTime::from_hms_opt(9, 30, 0));
+    assert_eq!(detail.effects, Effects::BOLD | Effects::UNDERLINE);
+
+    let range: DateRange = toml::from_str(
+        r#"
+        start = "2025-03-10"
+        end = "2025-03-14"
+        color = "blue"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        (range.start, range.end),
+        (date(2025, 3, 10), date(2025, 3, 14))
+    );
+    assert_eq!(range.description, None);
+
+    let err = toml::from_str::<DateDetail>("description = \"x\"\neffects = [\"blink\"]")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Invalid effect: 'blink'"), "{}", err);
+}
+
+#[test]
+fn test_calendar_json_round_trip() {
+    let calendar = CalendarBuilder::new()
+        .year(2025)
+        .today(date(2025, 1, 1))
+        .month_filter(MonthFilter::Single(3))
+        .week_start(WeekStart::Sunday)
+        .add_range(DateRange {
+            start: date(2025, 3, 10),
+            end: date(2025, 3, 14),
+            color: "blue".to_string(),
+            description: Some("Sprint".to_string()),
+            important: false,
+            pinned: false,
+            effects: Effects::ITALIC,
+            dim_weekends: None,
+        })
+        .add_color("brand", ColorValue::from_rgb(anstyle::RgbColor(10, 20, 30)))
+        .build();
+
+    let json = serde_json::to_value(&calendar).unwrap();
+    assert_eq!(json["week_start"], "sunday");
+    assert_eq!(json["month_filter"], serde_json::json!({ "single": 3 }));
+    assert_eq!(json["ranges"][0]["start"], "2025-03-10");
+    assert_eq!(json["ranges"][0]["effects"], serde_json::json!(["italic"]));
+    assert_eq!(json["colors"]["brand"]["normal"], "#0a141e");
+    assert_eq!(json["week_label_format"], "W{n:02}");
+
+    let back: compact_calendar_cli::models::Calendar = serde_json::from_value(json).unwrap();
+    assert_eq!(back.ranges, calendar.ranges);
+    assert_eq!(back.colors, calendar.colors);
+    assert_eq!(back.week_label_format, WeekLabelFormat::default());
+}
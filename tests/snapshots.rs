@@ -1,9 +1,12 @@
+use chrono::NaiveDate;
 use compact_calendar_cli::models::{
-    CalendarOptions, ColorMode, MonthFilter, PastDateDisplay, WeekStart, WeekendDisplay,
+    BorderStyle, Calendar, CalendarOptions, MonthFilter, WeekLabelFormat, WeekNumbering, WeekStart,
 };
-use compact_calendar_cli::rendering::CalendarRenderer;
+use compact_calendar_cli::rendering::{BorderChars, CalendarRenderer};
 use std::path::PathBuf;
 
+mod common;
+
 fn create_calendar_from_config(year: i32, config_path: &str) -> String {
     create_calendar_from_config_with_filter(year, config_path, MonthFilter::All)
 }
@@ -13,18 +16,14 @@ fn create_calendar_from_config_with_filter(
     config_path: &str,
     month_filter: MonthFilter,
 ) -> String {
-    let config = compact_calendar_cli::load_config(&PathBuf::from(config_path));
-    let options = CalendarOptions {
-        week_start: WeekStart::Monday,
-        weekend_display: WeekendDisplay::Normal,
-        color_mode: ColorMode::Normal,
-        past_date_display: PastDateDisplay::Normal,
-        month_filter,
-    };
-    let calendar = compact_calendar_cli::build_calendar(year, options, config);
+    let calendar = build_calendar_from_config(year, config_path, month_filter);
+    CalendarRenderer::new(&calendar).render_to_string()
+}
 
-    let renderer = CalendarRenderer::new(&calendar);
-    renderer.render_to_string()
+fn build_calendar_from_config(year: i32, config_path: &str, month_filter: MonthFilter) -> Calendar {
+    let config = compact_calendar_cli::load_config(&PathBuf::from(config_path)).unwrap();
+    let options = common::options(month_filter);
+    compact_calendar_cli::build_calendar(year, options, config)
 }
 
 #[test]
@@ -65,13 +64,11 @@ fn test_empty_2025() {
 
 #[test]
 fn test_sunday_start_2024() {
-    let config = compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml"));
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
     let options = CalendarOptions {
         week_start: WeekStart::Sunday,
-        weekend_display: WeekendDisplay::Normal,
-        color_mode: ColorMode::Normal,
-        past_date_display: PastDateDisplay::Normal,
-        month_filter: MonthFilter::All,
+        ..common::options(MonthFilter::All)
     };
     let calendar = compact_calendar_cli::build_calendar(2024, options, config);
 
@@ -124,7 +121,7 @@ fn test_single_month_february_2024_leap_year() {
 
 #[test]
 fn test_current_with_following_3_months_2026() {
-    // This simulates January + 3 following months = Jan, Feb, Mar, Apr
+    // Today is pinned to January, so this renders Jan, Feb, Mar, Apr
     let output = create_calendar_from_config_with_filter(
         2026,
         "tests/fixtures/empty.toml",
@@ -153,3 +150,533 @@ fn test_three_months_with_quarters_2024() {
     );
     insta::assert_snapshot!(output);
 }
+
+#[test]
+fn test_date_range_starting_mid_month_2026() {
+    let output = create_calendar_from_config_with_filter(
+        2026,
+        "tests/fixtures/simple.toml",
+        MonthFilter::DateRange(
+            NaiveDate::from_ymd_opt(2026, 3, 10).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 4, 20).unwrap(),
+        ),
+    );
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_date_range_starting_mid_week_after_month_start_2026() {
+    let output = create_calendar_from_config_with_filter(
+        2026,
+        "tests/fixtures/empty.toml",
+        MonthFilter::DateRange(
+            NaiveDate::from_ymd_opt(2026, 4, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 5, 10).unwrap(),
+        ),
+    );
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_render_month_to_string_march_2024() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let options = common::options(MonthFilter::All);
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+
+    let output = CalendarRenderer::new(&calendar).render_month_to_string(3);
+    assert!(output.contains("March"));
+    assert!(!output.contains("April"));
+    assert_eq!(
+        output.lines().filter(|line| line.starts_with("│W")).count(),
+        5
+    );
+    assert!(output.contains("03/17 - St. Patrick's Day"));
+}
+
+#[test]
+fn test_explain_layout_matches_render() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let options = CalendarOptions {
+        week_label_format: WeekLabelFormat::from_template("Week {n}").unwrap(),
+        ..common::options(MonthFilter::Single(2))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let renderer = CalendarRenderer::new(&calendar);
+    let explain = renderer.explain_layout();
+    let output = renderer.render_to_string();
+
+    let total_width: usize = explain
+        .lines()
+        .find_map(|line| line.strip_prefix("total_width: "))
+        .unwrap()
+        .parse()
+        .unwrap();
+    let week = output.lines().find(|line| line.contains("Week 2")).unwrap();
+    // Drop the annotation trailing the row's right border
+    let row = &week[..week.rfind('│').unwrap() + '│'.len_utf8()];
+    assert_eq!(row.chars().count(), total_width);
+
+    // The first week of February 2024 crosses from January at index 3
+    assert!(explain.contains("Week 1 2024-01-29: month_start_idx=Some((3, 2))"));
+    assert!(explain.contains("boundaries=[3]"));
+}
+
+#[test]
+fn test_date_range_across_new_year_2024() {
+    let output = create_calendar_from_config_with_filter(
+        2024,
+        "tests/fixtures/new_year.toml",
+        MonthFilter::DateRange(
+            NaiveDate::from_ymd_opt(2024, 12, 10).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 12).unwrap(),
+        ),
+    );
+
+    assert!(output.contains("December"));
+    assert!(output.contains("January"));
+    assert!(output.contains("01/01 - New Year's Day"));
+    assert_eq!(output.matches("Holiday Break").count(), 1);
+    // Rendering stops at the window end rather than Dec 31
+    let weeks: Vec<&str> = output.lines().filter(|l| l.starts_with("│W")).collect();
+    assert!(weeks
+        .last()
+        .unwrap()
+        .contains(" 06   07   08   09   10   11   12 "));
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_pinned_footer_january_2024() {
+    let output = create_calendar_from_config_with_filter(
+        2024,
+        "tests/fixtures/pinned.toml",
+        MonthFilter::Single(1),
+    );
+
+    let footer = &output[output.find("Pinned:").unwrap()..];
+    assert!(footer.contains("06/14 - Launch deadline"));
+    assert!(footer.contains("09/02 to 09/06 - Conference"));
+    assert!(!output.contains("Not pinned"));
+    // Unpinned events inside the window stay in the grid
+    assert!(!footer.contains("Kickoff"));
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_ascii_borders_match_unicode_layout() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let options = CalendarOptions {
+        today: NaiveDate::from_ymd_opt(2024, 2, 15).unwrap(),
+        border_style: BorderStyle::Ascii,
+        ..common::options(MonthFilter::CurrentWithFollowing(1))
+    };
+    let ascii = compact_calendar_cli::build_calendar(2024, options.clone(), config.clone());
+    let unicode = compact_calendar_cli::build_calendar(
+        2024,
+        CalendarOptions {
+            border_style: BorderStyle::Sharp,
+            ..options
+        },
+        config,
+    );
+    let ascii = CalendarRenderer::new(&ascii).render_to_string();
+    let unicode = CalendarRenderer::new(&unicode).render_to_string();
+
+    assert!(ascii.is_ascii(), "{}", ascii);
+    for (a, u) in ascii.lines().zip(unicode.lines()) {
+        assert_eq!(a.chars().count(), u.chars().count());
+    }
+    assert_eq!(ascii.lines().count(), unicode.lines().count());
+    insta::assert_snapshot!(ascii);
+}
+
+#[test]
+fn test_iso_week_numbers_with_month_filter() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/empty.toml")).unwrap();
+    let options = CalendarOptions {
+        week_numbering: WeekNumbering::Iso,
+        ..common::options(MonthFilter::Single(3))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options.clone(), config.clone());
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    // 2024-02-26, the first row of March's view, falls in ISO week 9
+    assert!(output.contains("│W09 March"), "{}", output);
+    assert!(output.contains("│W13          │ 25"), "{}", output);
+
+    // ISO weeks are Monday-based, so a Sunday start keeps counting rows
+    let sunday = CalendarOptions {
+        week_start: WeekStart::Sunday,
+        ..options
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, sunday, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(output.contains("│W01 March"), "{}", output);
+}
+
+#[test]
+fn test_iso_weeks_across_year_boundary() {
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/empty.toml")).unwrap();
+    let options = CalendarOptions {
+        week_numbering: WeekNumbering::Iso,
+        ..common::options(MonthFilter::DateRange(
+            date(2026, 12, 14),
+            date(2027, 1, 17),
+        ))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2026, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    // 2026 has 53 ISO weeks; the 53rd ends on 2027-01-03
+    assert!(output.contains("│W53 January  │ 28"), "{}", output);
+    assert!(output.contains("│W01          │ 04"), "{}", output);
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_iso_weeks_when_year_starts_midweek() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/empty.toml")).unwrap();
+    let options = CalendarOptions {
+        week_numbering: WeekNumbering::Iso,
+        ..common::options(MonthFilter::Single(1))
+    };
+    // 2027 starts on a Friday, so its first row is still in 2026's last week
+    let calendar = compact_calendar_cli::build_calendar(2027, options.clone(), config.clone());
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(output.contains("│W53 January"), "{}", output);
+    assert!(output.contains("│W01          │ 04"), "{}", output);
+    insta::assert_snapshot!(output);
+
+    // 2025 starts on a Wednesday, so its first row is already week 1
+    let calendar = compact_calendar_cli::build_calendar(2025, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(output.contains("│W01 January"), "{}", output);
+    assert!(output.contains("│W02          │ 06"), "{}", output);
+}
+
+#[test]
+fn test_saturday_start_march_2024() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let options = CalendarOptions {
+        week_start: WeekStart::from_name("saturday").unwrap(),
+        week_numbering: WeekNumbering::Iso,
+        ..common::options(MonthFilter::Single(3))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    assert!(output.contains("Sat  Sun  Mon  Tue  Wed  Thu  Fri │"));
+    // 2024-03-02 is a Saturday, so it starts a row
+    assert!(
+        output.contains("│W02          │ 02   03   04"),
+        "{}",
+        output
+    );
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_week_start_from_name() {
+    assert_eq!(WeekStart::from_name("mon"), Ok(WeekStart::Monday));
+    assert_eq!(WeekStart::from_name("Sunday"), Ok(WeekStart::Sunday));
+    assert_eq!(
+        WeekStart::from_name("sat"),
+        Ok(WeekStart::Custom(chrono::Weekday::Sat))
+    );
+    assert_eq!(
+        WeekStart::from_name("someday"),
+        Err("Invalid week start: someday (expected a weekday, e.g. saturday)".to_string())
+    );
+}
+
+#[test]
+fn test_month_range_jan_mar_2024() {
+    let filter = MonthFilter::from_cli_args(Some("jan-mar"), None).unwrap();
+    assert_eq!(
+        filter,
+        MonthFilter::from_cli_args(Some("1-3"), None).unwrap()
+    );
+    let output =
+        create_calendar_from_config_with_filter(2024, "tests/fixtures/quarters.toml", filter);
+
+    assert!(output.contains("January"));
+    assert!(output.contains("February"));
+    assert!(output.contains("March"));
+    assert!(!output.contains("April"));
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_month_names_ignore_case() {
+    let parse = |input| MonthFilter::from_cli_args(Some(input), None);
+    for input in [
+        "march", "March", "MARCH", "mar", "Mar", "MAR", "3", "03", " mar ",
+    ] {
+        assert_eq!(parse(input), Ok(MonthFilter::Single(3)), "{}", input);
+    }
+    assert_eq!(parse("Sept"), Ok(MonthFilter::Single(9)));
+    assert_eq!(parse("DEC"), Ok(MonthFilter::Single(12)));
+    assert_eq!(parse("Current"), Ok(MonthFilter::Current));
+    assert_eq!(parse("Jan-MAR"), Ok(MonthFilter::MonthRange(1, 3)));
+
+    assert_eq!(
+        parse("marhc"),
+        Err(
+            "Invalid month: 'marhc'. Use 1-12, a month name or its three-letter \
+             abbreviation (e.g. 'march' or 'mar'), or 'current'"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        parse("13"),
+        Err("Month number must be 1-12, got 13".to_string())
+    );
+}
+
+#[test]
+fn test_month_range_errors() {
+    let wrapped = MonthFilter::from_cli_args(Some("nov-feb"), None).unwrap_err();
+    assert_eq!(
+        wrapped,
+        "Month range 'nov-feb' wraps past December; use --from/--to instead"
+    );
+    assert!(MonthFilter::from_cli_args(Some("jan-13"), None).is_err());
+    assert!(MonthFilter::from_cli_args(Some("current-mar"), None).is_err());
+}
+
+#[test]
+fn test_quarter_filter() {
+    let quarter = MonthFilter::from_quarter(2).unwrap();
+    let today = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+    assert_eq!(
+        quarter.get_date_range(2024, today),
+        (
+            NaiveDate::from_ymd_opt(2024, 4, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 6, 30).unwrap()
+        )
+    );
+    assert!(!quarter.should_display_month(3, 2024, today));
+    assert!(quarter.should_display_month(6, 2024, today));
+
+    // Renders the same as the equivalent month range
+    let apr_jun = MonthFilter::from_cli_args(Some("apr-jun"), None).unwrap();
+    assert_eq!(
+        create_calendar_from_config_with_filter(2024, "tests/fixtures/quarters.toml", quarter),
+        create_calendar_from_config_with_filter(2024, "tests/fixtures/quarters.toml", apr_jun)
+    );
+
+    assert_eq!(
+        MonthFilter::from_quarter(4)
+            .unwrap()
+            .get_month_range(2024, today),
+        (10, 12)
+    );
+    assert_eq!(
+        MonthFilter::from_quarter(5),
+        Err("Quarter must be 1-4, got 5".to_string())
+    );
+    assert!(MonthFilter::from_quarter(0).is_err());
+}
+
+#[test]
+fn test_footnotes_key_annotations_to_days() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let options = CalendarOptions {
+        footnotes: true,
+        ..common::options(MonthFilter::Single(2))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2025, options, config);
+
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(
+        output.contains("│ 10¹  11   12   13   14²  15   16 │"),
+        "{}",
+        output
+    );
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_reverse_mirrors_month_borders() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let options = CalendarOptions {
+        reverse: true,
+        ..common::options(MonthFilter::MonthRange(1, 2))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2025, options.clone(), config.clone());
+
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    let rows: Vec<&str> = output.lines().skip(4).collect();
+    // The last week is on top, and February closes off above January
+    assert!(rows[1].starts_with("│W09 March    │ 24"), "{}", output);
+    assert_eq!(
+        rows[6],
+        "│             ├────────────────────────┐         │"
+    );
+    assert_eq!(
+        rows[8],
+        "│             │                        └─────────┤"
+    );
+    insta::assert_snapshot!(output);
+
+    // Rounded corners face the same way
+    let options = CalendarOptions {
+        border_style: BorderStyle::Rounded,
+        ..options
+    };
+    let calendar = compact_calendar_cli::build_calendar(2025, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    let rows: Vec<&str> = output.lines().skip(4).collect();
+    assert_eq!(
+        rows[6],
+        "│             ├────────────────────────╮         │"
+    );
+    assert_eq!(
+        rows[8],
+        "│             │                        ╰─────────┤"
+    );
+}
+
+#[test]
+fn test_colored_single_month_january_2026() {
+    // Today (01/15) is underlined on top of its event color
+    let calendar =
+        build_calendar_from_config(2026, "tests/fixtures/simple.toml", MonthFilter::Single(1));
+    let output = CalendarRenderer::new(&calendar).render_colored_to_string();
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_weeks_filter() {
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    let today = date(2026, 1, 15);
+    let weeks = MonthFilter::from_weeks("10-12", 2026).unwrap();
+    assert_eq!(weeks, MonthFilter::Weeks(10, 12));
+    assert_eq!(
+        weeks.get_date_range(2026, today),
+        (date(2026, 3, 2), date(2026, 3, 22))
+    );
+    assert!(weeks.should_display_month(3, 2026, today));
+    assert!(!weeks.should_display_month(4, 2026, today));
+
+    // Weeks are counted in the calendar's year, not today's: week 22 of
+    // 2026 stays in May, while week 22 of 2025 runs into June
+    let in_2025 = date(2025, 6, 1);
+    let week_22 = MonthFilter::Weeks(22, 22);
+    assert_eq!(week_22.get_month_range(2026, in_2025), (5, 5));
+    assert_eq!(week_22.get_month_range(2025, in_2025), (5, 6));
+    assert!(!week_22.should_display_month(6, 2026, in_2025));
+
+    // Week 1 of 2026 starts in December; a Sunday row belongs to the ISO
+    // week of the Monday after it
+    let first = MonthFilter::Weeks(1, 1);
+    assert_eq!(first.get_date_range(2026, today).0, date(2025, 12, 29));
+    assert!(first.should_render_week(2026, date(2025, 12, 28)));
+    assert!(!first.should_render_week(2026, date(2026, 1, 4)));
+    assert!(!first.should_render_week(2025, date(2025, 12, 29)));
+
+    let output = create_calendar_from_config_with_filter(2026, "tests/fixtures/simple.toml", weeks);
+    let rows: Vec<&str> = output
+        .lines()
+        .filter(|line| line.starts_with("│W"))
+        .collect();
+    assert_eq!(rows.len(), 3, "{}", output);
+    // Numbered from the first row, as sequential numbering does
+    assert!(rows[0].starts_with("│W01 March    │ 02 "), "{}", output);
+
+    assert_eq!(MonthFilter::iso_weeks_in(2025), 52);
+    assert_eq!(MonthFilter::iso_weeks_in(2026), 53);
+    assert_eq!(
+        MonthFilter::from_weeks("53", 2025),
+        Err("2025 has 52 ISO weeks, got week 53".to_string())
+    );
+    assert!(MonthFilter::from_weeks("0-3", 2026).is_err());
+    assert!(MonthFilter::from_weeks("15-10", 2026).is_err());
+    assert!(MonthFilter::from_weeks("ten", 2026).is_err());
+}
+
+#[test]
+fn test_only_active_skips_quiet_months() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/sparse.toml")).unwrap();
+    let options = CalendarOptions {
+        week_numbering: WeekNumbering::Iso,
+        only_active: true,
+        ..common::options(MonthFilter::MonthRange(1, 7))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2026, options, config);
+
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(!output.contains("March"), "{}", output);
+    assert!(!output.contains("June"), "{}", output);
+    // February's first week closes off where April's opens
+    assert!(output.contains(
+        "│W05 February │ 26   27   28   29   30   31 │ 01 │\n\
+         │             ├─────────┬───────────────────┴────┤\n\
+         │W14 April    │ 30   31 │ 01   02   03   04   05 │\n"
+    ));
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_border_styles_swap_glyphs_one_for_one() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let render = |border_style| {
+        let options = CalendarOptions {
+            border_style,
+            ..common::options(MonthFilter::MonthRange(1, 2))
+        };
+        let calendar = compact_calendar_cli::build_calendar(2025, options, config.clone());
+        CalendarRenderer::new(&calendar).render_to_string()
+    };
+    let sharp = render(BorderStyle::Sharp);
+
+    for style in [BorderStyle::Rounded, BorderStyle::Double] {
+        let output = render(style);
+        let chars = BorderChars::for_style(style);
+        let sharp_chars = BorderChars::SHARP;
+        let pairs = [
+            (chars.horizontal, sharp_chars.horizontal),
+            (chars.vertical, sharp_chars.vertical),
+            (chars.top_left, sharp_chars.top_left),
+            (chars.top_right, sharp_chars.top_right),
+            (chars.bottom_left, sharp_chars.bottom_left),
+            (chars.bottom_right, sharp_chars.bottom_right),
+            (chars.tee_right, sharp_chars.tee_right),
+            (chars.tee_left, sharp_chars.tee_left),
+            (chars.tee_down, sharp_chars.tee_down),
+            (chars.tee_up, sharp_chars.tee_up),
+            (chars.cross, sharp_chars.cross),
+        ];
+        let back: String = output
+            .chars()
+            .map(|c| {
+                pairs
+                    .iter()
+                    .find(|(s, _)| *s == c)
+                    .map_or(c, |(_, sharp)| *sharp)
+            })
+            .collect();
+        assert_eq!(back, sharp, "{:?}\n{}", style, output);
+    }
+
+    let rounded = render(BorderStyle::Rounded);
+    assert!(rounded.starts_with("╭────"), "{}", rounded);
+    assert!(rounded.contains("╯"), "{}", rounded);
+    insta::assert_snapshot!(render(BorderStyle::Double));
+
+    assert_eq!(BorderStyle::from_name("Rounded"), Ok(BorderStyle::Rounded));
+    assert_eq!(
+        BorderStyle::from_name("dotted"),
+        Err("Invalid border style: dotted (expected sharp, rounded, double or ascii)".to_string())
+    );
+}
@@ -0,0 +1,1476 @@
+use anstyle::{Color, Effects, RgbColor};
+use chrono::{NaiveDate, Weekday};
+use compact_calendar_cli::models::{
+    AnnotationPosition, AnnotationStyle, BorderStyle, Calendar, CalendarOptions, ColorMode,
+    EventFilter, MonthFilter, PastDateDisplay, TerminalBackground, WeekendDisplay,
+    DEFAULT_WEEKEND_DAYS,
+};
+use compact_calendar_cli::rendering::{
+    dim_rgb, CalendarRenderer, ColorChoice, ColorPalette, ColorValue, BUILTIN_COLORS,
+};
+use std::path::PathBuf;
+
+mod common;
+
+fn build_calendar(year: i32, config_path: &str, month_filter: MonthFilter) -> Calendar {
+    let config = compact_calendar_cli::load_config(&PathBuf::from(config_path)).unwrap();
+    compact_calendar_cli::build_calendar(year, common::options(month_filter), config)
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_important_event_is_emphasized() {
+    let calendar = build_calendar(
+        2024,
+        "tests/fixtures/important.toml",
+        MonthFilter::Single(3),
+    );
+    let renderer = CalendarRenderer::new(&calendar);
+
+    let important = renderer.day_style(date(2024, 3, 15));
+    assert!(important.get_effects().contains(Effects::BOLD));
+    let regular = renderer.day_style(date(2024, 3, 20));
+    assert!(!regular.get_effects().contains(Effects::BOLD));
+
+    let output = renderer.render_to_string();
+    assert!(output.contains("03/15 - PROJECT ALPHA DEADLINE"));
+    assert!(output.contains("03/20 - Team Lunch"));
+    assert!(output.contains("*15*"));
+}
+
+#[test]
+fn test_annotation_position_below() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let options = CalendarOptions {
+        annotation_position: AnnotationPosition::Below,
+        ..common::options(MonthFilter::Single(4))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    let lines: Vec<&str> = output.lines().collect();
+    let row = lines
+        .iter()
+        .position(|line| line.contains(" April "))
+        .unwrap();
+    assert!(lines[row].ends_with("07 │"));
+    assert_eq!(lines[row + 1], "│             │ 04/01 - April Fools");
+}
+
+#[test]
+fn test_range_caps() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let options = CalendarOptions {
+        range_caps: true,
+        ..common::options(MonthFilter::Single(4))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let renderer = CalendarRenderer::new(&calendar);
+
+    // "Tax Season Crunch" runs 04/15 to 04/30
+    let purple = ColorPalette::get_color_value("purple").unwrap().normal;
+    let cap = Some(Color::Rgb(ColorValue::cap(purple)));
+    let base = Some(Color::Rgb(purple));
+    assert_eq!(renderer.day_style(date(2024, 4, 15)).get_bg_color(), cap);
+    assert_eq!(renderer.day_style(date(2024, 4, 16)).get_bg_color(), base);
+    assert_eq!(renderer.day_style(date(2024, 4, 29)).get_bg_color(), base);
+    assert_eq!(renderer.day_style(date(2024, 4, 30)).get_bg_color(), cap);
+}
+
+#[test]
+fn test_only_ranges_drops_detail_colors() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let options = CalendarOptions {
+        event_filter: EventFilter::OnlyRanges,
+        ..common::options(MonthFilter::Single(4))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let renderer = CalendarRenderer::new(&calendar);
+
+    assert_eq!(renderer.day_style(date(2024, 4, 1)).get_bg_color(), None);
+    assert!(renderer
+        .day_style(date(2024, 4, 16))
+        .get_bg_color()
+        .is_some());
+
+    let output = renderer.render_to_string();
+    assert!(output.contains("04/15 to 04/30 - Tax Season Crunch"));
+    assert!(!output.contains("April Fools"));
+}
+
+#[test]
+fn test_color_aliases() {
+    let calendar = build_calendar(2024, "tests/fixtures/aliases.toml", MonthFilter::Single(3));
+    let renderer = CalendarRenderer::new(&calendar);
+
+    let red = Some(Color::Rgb(
+        ColorPalette::get_color_value("red").unwrap().normal,
+    ));
+    assert_eq!(renderer.day_style(date(2024, 3, 15)).get_bg_color(), red);
+    assert_eq!(renderer.day_style(date(2024, 3, 19)).get_bg_color(), red);
+}
+
+#[test]
+fn test_cyclic_color_alias_is_rejected() {
+    let err = compact_calendar_cli::parse_config(
+        r#"
+        [aliases]
+        urgent = "alarm"
+        alarm = "urgent"
+        "#,
+    )
+    .unwrap_err();
+    assert!(err.contains("cycle"), "{}", err);
+
+    let err = compact_calendar_cli::parse_config(
+        r#"
+        [aliases]
+        urgent = "no-such-color"
+        "#,
+    )
+    .unwrap_err();
+    assert!(err.contains("unknown color"), "{}", err);
+}
+
+#[test]
+fn test_unknown_color_names_are_reported() {
+    let config = compact_calendar_cli::parse_config(
+        r##"
+        [aliases]
+        urgent = "red"
+
+        [colors]
+        brand = "#102030"
+
+        [dates."2024-03-05"]
+        description = "Launch"
+        color = "gren"
+
+        [dates."2024-03-06"]
+        description = "Review"
+        color = "urgent"
+
+        [[ranges]]
+        start = "2024-03-11"
+        end = "2024-03-15"
+        color = "blu"
+        description = "Sprint"
+
+        [[ranges]]
+        start = "2024-03-18"
+        end = "2024-03-22"
+        color = "brand"
+
+        [[milestone]]
+        date = "2024-03-29"
+        label = "Freeze"
+        color = "#abcdef"
+        "##,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.unknown_colors(),
+        vec![
+            "Unknown color 'gren' on 2024-03-05",
+            "Unknown color 'blu' on Range 1 (Sprint)",
+        ]
+    );
+}
+
+#[test]
+fn test_terminal_background_decision() {
+    assert_eq!(
+        TerminalBackground::from_rgb(0, 0, 0),
+        TerminalBackground::Dark
+    );
+    assert_eq!(
+        TerminalBackground::from_rgb(40, 42, 54),
+        TerminalBackground::Dark
+    );
+    assert_eq!(
+        TerminalBackground::from_rgb(253, 246, 227),
+        TerminalBackground::Light
+    );
+    assert_eq!(
+        TerminalBackground::from_rgb(255, 255, 255),
+        TerminalBackground::Light
+    );
+    assert!(TerminalBackground::Dark.dimming_reads_well());
+    assert!(!TerminalBackground::Light.dimming_reads_well());
+
+    let reply = "\x1b]11;rgb:fdfd/f6f6/e3e3\x1b\\";
+    assert_eq!(
+        compact_calendar_cli::terminal::parse_osc11_response(reply),
+        Some((253, 246, 227))
+    );
+    assert_eq!(
+        compact_calendar_cli::terminal::parse_osc11_response("\x1b]11;rgb:0/0/0\x07"),
+        Some((0, 0, 0))
+    );
+    assert_eq!(
+        compact_calendar_cli::terminal::parse_osc11_response(""),
+        None
+    );
+}
+
+#[test]
+fn test_range_duration_annotation() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+        [[ranges]]
+        start = "2024-03-11"
+        end = "2024-03-15"
+        color = "blue"
+        description = "Offsite"
+        "#,
+    )
+    .unwrap();
+
+    let options = CalendarOptions {
+        range_duration: true,
+        ..common::options(MonthFilter::Single(3))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config.clone());
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(
+        output.contains("03/11 to 03/15 - Offsite (5 days)"),
+        "{}",
+        output
+    );
+
+    let options = CalendarOptions {
+        range_duration: true,
+        range_workdays: true,
+        ..common::options(MonthFilter::Single(3))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(output.contains("Offsite (5 days, 5 working)"), "{}", output);
+}
+
+#[test]
+fn test_severity_colors() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+        [dates."2024-03-04"]
+        description = "Sev 5 outage"
+        severity = 5
+
+        [dates."2024-03-05"]
+        description = "Sev 1 blip"
+        severity = 1
+
+        [dates."2024-03-06"]
+        description = "Minor"
+        severity = 2
+
+        [dates."03-06"]
+        description = "Major"
+        severity = 4
+        "#,
+    )
+    .unwrap();
+    let calendar =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::Single(3)), config);
+    let renderer = CalendarRenderer::new(&calendar);
+
+    let bg = |name: &str| {
+        Some(Color::Rgb(
+            ColorPalette::get_color_value(name).unwrap().normal,
+        ))
+    };
+    assert_eq!(
+        renderer.day_style(date(2024, 3, 4)).get_bg_color(),
+        bg("red")
+    );
+    assert_eq!(
+        renderer.day_style(date(2024, 3, 5)).get_bg_color(),
+        bg("green")
+    );
+    assert_eq!(
+        renderer.day_style(date(2024, 3, 6)).get_bg_color(),
+        bg("light_red")
+    );
+    assert_eq!(calendar.details[&date(2024, 3, 6)][0].description, "Major");
+
+    let err = compact_calendar_cli::parse_config("[dates.\"03-01\"]\nseverity = 6\n").unwrap_err();
+    assert!(err.contains("must be 1-5"), "{}", err);
+}
+
+#[test]
+fn test_week_heat() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+        [dates."2024-03-05"]
+        description = "Standup review"
+
+        [dates."2024-03-06"]
+        description = "Design review"
+
+        [dates."2024-03-07"]
+        description = "Retro"
+        "#,
+    )
+    .unwrap();
+    let options = CalendarOptions {
+        week_heat: true,
+        ..common::options(MonthFilter::Single(3))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    let heat = |week: &str| {
+        let row = output.lines().find(|line| line.contains(week)).unwrap();
+        row.chars().nth(1).unwrap()
+    };
+    assert_eq!(heat("W02"), '▓');
+    assert_eq!(heat("W03"), ' ');
+    assert!(output.contains("│▓ W02          │ 04   05"), "{}", output);
+}
+
+#[test]
+fn test_annotation_style_long() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+        [dates."2024-03-21"]
+        description = "Spring kickoff"
+
+        [dates."2024-03-22"]
+        description = "Offsite"
+
+        [dates."2024-03-12"]
+        description = "Review"
+
+        [[ranges]]
+        start = "2024-03-01"
+        end = "2024-03-03"
+        color = "blue"
+        description = "Trip"
+        "#,
+    )
+    .unwrap();
+    let options = CalendarOptions {
+        annotation_style: AnnotationStyle::Long,
+        ..common::options(MonthFilter::Single(3))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    assert!(output.contains("March 21st - Spring kickoff"), "{}", output);
+    assert!(output.contains("March 22nd - Offsite"), "{}", output);
+    assert!(output.contains("March 12th - Review"), "{}", output);
+    assert!(
+        output.contains("March 1st to March 3rd - Trip"),
+        "{}",
+        output
+    );
+    assert!(!output.contains("03/21"));
+}
+
+#[test]
+fn test_freebusy_marks_busy_days() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+        [dates."2024-03-05"]
+        description = "Dentist"
+
+        [[ranges]]
+        start = "2024-03-08"
+        end = "2024-03-09"
+        color = "blue"
+        description = "Trip"
+        "#,
+    )
+    .unwrap();
+    let calendar =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::Single(3)), config);
+    let output = CalendarRenderer::new(&calendar).render_freebusy_to_string();
+
+    // The week of 03/04: Tuesday has an event, Friday and Saturday a range
+    assert!(output.contains("W02 03/04 ░█░░██░"), "{}", output);
+    assert!(output.contains("W03 03/11 ░░░░░░░"), "{}", output);
+    assert_eq!(output.lines().next().unwrap().trim(), "MTWTFSS");
+}
+
+#[test]
+fn test_concurrent_plain_and_colored_renders() {
+    let calendar = build_calendar(2024, "tests/fixtures/simple.toml", MonthFilter::Single(3));
+    let no_color = std::env::var("NO_COLOR").ok();
+
+    std::thread::scope(|scope| {
+        let plain = scope.spawn(|| {
+            (0..50)
+                .map(|_| CalendarRenderer::new(&calendar).render_to_string())
+                .collect::<Vec<_>>()
+        });
+        let colored = scope.spawn(|| {
+            (0..50)
+                .map(|_| {
+                    let mut output = Vec::new();
+                    CalendarRenderer::new(&calendar)
+                        .with_colors(true)
+                        .render_to_writer(&mut output)
+                        .unwrap();
+                    String::from_utf8(output).unwrap()
+                })
+                .collect::<Vec<_>>()
+        });
+
+        assert!(plain
+            .join()
+            .unwrap()
+            .iter()
+            .all(|output| !output.contains('\x1b')));
+        assert!(colored
+            .join()
+            .unwrap()
+            .iter()
+            .all(|output| output.contains('\x1b')));
+    });
+
+    // Rendering never touches the process environment
+    assert_eq!(std::env::var("NO_COLOR").ok(), no_color);
+}
+
+#[test]
+fn test_custom_colors_from_config() {
+    let config = compact_calendar_cli::parse_config(
+        r##"
+        [colors]
+        meeting = [1, 2, 3]
+        red = "#102030"
+
+        [dates."2024-03-05"]
+        description = "Planning"
+        color = "meeting"
+
+        [dates."2024-03-06"]
+        description = "Deadline"
+        color = "red"
+        "##,
+    )
+    .unwrap();
+    let calendar =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::Single(3)), config);
+    let mut output = Vec::new();
+    CalendarRenderer::new(&calendar)
+        .with_colors(true)
+        .render_to_writer(&mut output)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("\x1b[48;2;1;2;3m"), "{}", output);
+    // The user's red replaces the builtin one
+    assert!(output.contains("\x1b[48;2;16;32;48m"), "{}", output);
+    let builtin_red = ColorPalette::get_color_value("red").unwrap().normal;
+    assert!(!output.contains(&format!(
+        "48;2;{};{};{}m",
+        builtin_red.0, builtin_red.1, builtin_red.2
+    )));
+}
+
+#[test]
+fn test_invalid_custom_color() {
+    let err = compact_calendar_cli::parse_config(
+        r##"
+        [colors]
+        meeting = "#12345"
+        "##,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        "Invalid config: Color 'meeting': Invalid hex color: #12345 (expected #rrggbb)"
+    );
+}
+
+#[test]
+fn test_inline_hex_colors() {
+    let config = compact_calendar_cli::parse_config(
+        r##"
+        [dates."2024-03-05"]
+        description = "Planning"
+        color = "#ff8f40"
+
+        [[ranges]]
+        start = "2024-03-09"
+        end = "2024-03-10"
+        color = "#5080A0"
+        "##,
+    )
+    .unwrap();
+    let options = CalendarOptions {
+        weekend_display: WeekendDisplay::Dimmed,
+        ..common::options(MonthFilter::Single(3))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let renderer = CalendarRenderer::new(&calendar).with_colors(true);
+
+    let weekday = renderer.day_style(date(2024, 3, 5));
+    assert_eq!(
+        weekday.get_bg_color(),
+        Some(Color::Rgb(anstyle::RgbColor(255, 143, 64)))
+    );
+    // Weekends take the dimmed shade, about 70% of each channel
+    let weekend = renderer.day_style(date(2024, 3, 9));
+    assert_eq!(
+        weekend.get_bg_color(),
+        Some(Color::Rgb(anstyle::RgbColor(56, 89, 112)))
+    );
+}
+
+#[test]
+fn test_invalid_inline_hex_color() {
+    let err = compact_calendar_cli::parse_config(
+        r##"
+        [dates."2024-03-05"]
+        description = "Planning"
+        color = "#ff8f4"
+        "##,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        "Invalid config: Invalid hex color: #ff8f4 (expected #rrggbb)"
+    );
+}
+
+#[test]
+fn test_width_wraps_annotations() {
+    let options = CalendarOptions {
+        width: Some(70),
+        ..common::options(MonthFilter::Single(3))
+    };
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    assert!(
+        output.lines().all(|line| line.chars().count() <= 70),
+        "{}",
+        output
+    );
+    let mut lines = output.lines().skip_while(|line| !line.contains("03/15"));
+    let busy = lines.next().unwrap();
+    assert!(busy.ends_with(','), "{}", busy);
+    // The grid itself keeps its fixed columns
+    assert!(busy.starts_with("│W03          │ 11   12   13   14   15   16   17 │"));
+    // The rest continues under the annotation column
+    let continued = lines.next().unwrap();
+    assert!(
+        continued.starts_with("│             │                                  │03/17"),
+        "{}",
+        continued
+    );
+    // Short annotations are left alone
+    assert!(output.contains("│W01 March    │ 26   27   28   29 │ 01   02   03 │\n"));
+}
+
+#[test]
+fn test_max_annotation_width_wraps_below() {
+    let options = CalendarOptions {
+        max_annotation_width: Some(30),
+        annotation_position: AnnotationPosition::Below,
+        ..common::options(MonthFilter::Single(3))
+    };
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    let annotations: Vec<&str> = output
+        .lines()
+        .filter(|line| line.starts_with("│             │ 03/"))
+        .collect();
+    assert_eq!(annotations.len(), 2, "{}", output);
+    assert!(annotations[0].ends_with(','), "{}", output);
+}
+
+#[test]
+fn test_wrap_items() {
+    use compact_calendar_cli::formatting::wrap_items;
+
+    let items: Vec<String> = ["03/15 - Deadline", "03/17 - Party", "03/18 - Trip"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert_eq!(
+        wrap_items(&items, 80),
+        vec!["03/15 - Deadline, 03/17 - Party, 03/18 - Trip"]
+    );
+    assert_eq!(
+        wrap_items(&items, 32),
+        vec!["03/15 - Deadline, 03/17 - Party,", "03/18 - Trip"]
+    );
+    assert_eq!(
+        wrap_items(&items, 10),
+        vec!["03/15 - …,", "03/17 - …,", "03/18 - T…"]
+    );
+    assert_eq!(wrap_items(&[], 10), vec![""]);
+}
+
+#[test]
+fn test_truncate_display_keeps_styling() {
+    use compact_calendar_cli::formatting::{display_width, truncate_display};
+
+    assert_eq!(truncate_display("03/17 - Party", 20), "03/17 - Party");
+    assert_eq!(truncate_display("03/17 - Party", 8), "03/17 -…");
+    let styled = "\x1b[1m03/17 - Party\x1b[0m";
+    let cut = truncate_display(styled, 8);
+    assert_eq!(cut, "\x1b[1m03/17 -…\x1b[0m");
+    assert_eq!(display_width(&cut), 8);
+}
+
+#[test]
+fn test_today_override_styles_past_and_today() {
+    let options = CalendarOptions {
+        past_date_display: PastDateDisplay::Strikethrough,
+        ..common::options(MonthFilter::Single(1))
+    };
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let calendar = compact_calendar_cli::build_calendar(2026, options, config);
+    let mut output = Vec::new();
+    CalendarRenderer::new(&calendar)
+        .with_colors(true)
+        .render_to_writer(&mut output)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    // Rendering uses the pinned date, not the system clock
+    let week = output
+        .lines()
+        .find(|line| line.starts_with("│W03"))
+        .unwrap();
+    assert!(week.contains("\x1b[9m14\x1b[0m"), "{}", week);
+    assert!(week.contains("\x1b[4m"), "{}", week);
+    assert!(week.contains("   16   17   18 │"), "{}", week);
+}
+
+#[test]
+fn test_several_events_on_one_date() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+        [[dates."2024-03-06"]]
+        description = "Standup"
+
+        [[dates."2024-03-06"]]
+        description = "Launch"
+        color = "red"
+        important = true
+
+        [dates."2024-03-07"]
+        description = "Review"
+        color = "blue"
+        "#,
+    )
+    .unwrap();
+    let calendar =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::Single(3)), config);
+
+    assert_eq!(calendar.details[&date(2024, 3, 6)].len(), 2);
+    // The first event with a color colors the day
+    assert_eq!(calendar.color_for(date(2024, 3, 6)), Some("red"));
+    assert!(calendar.is_important(date(2024, 3, 6)));
+    assert_eq!(calendar.stats().events, 3);
+
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(
+        output.contains("03/06 - Standup, 03/06 - LAUNCH, 03/07 - Review"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_past_colors_dim() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let blue = ColorPalette::get_color_value("blue").unwrap();
+
+    for (display, struck) in [
+        (PastDateDisplay::Dimmed, false),
+        (PastDateDisplay::StrikethroughDimmed, true),
+    ] {
+        let options = CalendarOptions {
+            past_date_display: display,
+            ..common::options(MonthFilter::Single(1))
+        };
+        let calendar = compact_calendar_cli::build_calendar(2026, options, config.clone());
+        let renderer = CalendarRenderer::new(&calendar);
+
+        // Monday 01/05 is in the past, inside the blue range
+        let past = renderer.day_style(date(2026, 1, 5));
+        assert_eq!(past.get_bg_color(), Some(Color::Rgb(blue.dimmed)));
+        assert_eq!(past.get_effects().contains(Effects::STRIKETHROUGH), struck);
+    }
+
+    assert_eq!(
+        PastDateDisplay::from_flags(true, false),
+        PastDateDisplay::Normal
+    );
+    assert_eq!(
+        PastDateDisplay::from_flags(false, true),
+        PastDateDisplay::StrikethroughDimmed
+    );
+}
+
+fn palette_bg(name: &str) -> Option<Color> {
+    Some(Color::Rgb(
+        ColorPalette::get_color_value(name).unwrap().normal,
+    ))
+}
+
+#[test]
+fn test_today_color_overrides_date_color() {
+    let options = CalendarOptions {
+        today_color: Some("red".to_string()),
+        ..common::options(MonthFilter::Single(1))
+    };
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let calendar = compact_calendar_cli::build_calendar(2026, options, config);
+    let renderer = CalendarRenderer::new(&calendar);
+
+    // 01/15 has its own color in the config
+    let today = renderer.day_style(date(2026, 1, 15));
+    assert_eq!(today.get_bg_color(), palette_bg("red"));
+    assert!(today.get_effects().contains(Effects::UNDERLINE));
+    assert_eq!(renderer.day_style(date(2026, 1, 14)).get_bg_color(), None);
+}
+
+#[test]
+fn test_today_color_respects_work_mode() {
+    let options = CalendarOptions {
+        today: date(2026, 1, 17),
+        today_color: Some("red".to_string()),
+        color_mode: ColorMode::Work,
+        ..common::options(MonthFilter::Single(1))
+    };
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+    let calendar = compact_calendar_cli::build_calendar(2026, options, config);
+    let renderer = CalendarRenderer::new(&calendar);
+
+    // Saturdays stay uncolored in work mode
+    assert_eq!(renderer.day_style(date(2026, 1, 17)).get_bg_color(), None);
+}
+
+#[test]
+fn test_today_color_from_settings() {
+    let config = || compact_calendar_cli::parse_config("[settings]\ntoday_color = \"purple\"");
+    let calendar = compact_calendar_cli::build_calendar(
+        2026,
+        common::options(MonthFilter::Single(1)),
+        config().unwrap(),
+    );
+    let style = CalendarRenderer::new(&calendar).day_style(date(2026, 1, 15));
+    assert_eq!(style.get_bg_color(), palette_bg("purple"));
+
+    // The command line takes precedence
+    let options = CalendarOptions {
+        today_color: Some("green".to_string()),
+        ..common::options(MonthFilter::Single(1))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2026, options, config().unwrap());
+    let style = CalendarRenderer::new(&calendar).day_style(date(2026, 1, 15));
+    assert_eq!(style.get_bg_color(), palette_bg("green"));
+}
+
+#[test]
+fn test_detail_times_in_annotations() {
+    let calendar = build_calendar(2024, "tests/fixtures/times.toml", MonthFilter::Single(3));
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    assert!(
+        output.contains("03/12 14:00 - Planning, 03/13 - All day offsite, 03/14 09:30 - Standup"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_invalid_detail_time() {
+    let err = compact_calendar_cli::parse_config(
+        r#"
+        [dates."2024-03-14"]
+        description = "Standup"
+        time = "9:30am"
+        "#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        "Invalid config: Time for 2024-03-14: Invalid time: 9:30am (expected HH:MM)"
+    );
+}
+
+#[test]
+fn test_annotations_sorted_by_start_date() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+        [dates."2024-03-13"]
+        description = "Review"
+
+        [dates."2024-03-11"]
+        description = "Kickoff"
+
+        [[ranges]]
+        start = "2024-03-11"
+        end = "2024-03-12"
+        color = "blue"
+        description = "Sprint"
+
+        [[ranges]]
+        start = "2024-03-06"
+        end = "2024-03-12"
+        color = "green"
+        description = "Trip"
+        "#,
+    )
+    .unwrap();
+    let calendar =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::Single(3)), config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+
+    // A detail beats a range on the same day; ranges are listed once, where they start
+    assert!(
+        output.contains(
+            "│ 11   12   13   14   15   16   17 │03/11 - Kickoff, 03/11 to 03/12 - Sprint, 03/13 - Review\n"
+        ),
+        "{}",
+        output
+    );
+    assert!(output.contains("│ 04   05   06   07   08   09   10 │03/06 to 03/12 - Trip\n"));
+}
+
+#[test]
+fn test_weeks_match_rendered_rows() {
+    let calendar = build_calendar(2024, "tests/fixtures/simple.toml", MonthFilter::Single(3));
+    let weeks: Vec<_> = calendar.weeks().collect();
+
+    assert_eq!(weeks.first().unwrap().dates[0], date(2024, 2, 26));
+    assert_eq!(weeks.last().unwrap().dates[6], date(2024, 3, 31));
+    assert!(weeks
+        .iter()
+        .all(|week| calendar.get_weekday_num(week.dates[0]) == 0));
+    assert_eq!(weeks[0].month_start_idx, Some((4, 3)));
+
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    let rows = output.lines().filter(|line| line.starts_with("│W")).count();
+    assert_eq!(weeks.len(), rows);
+}
+
+#[test]
+fn test_inverted_range_rejected() {
+    let err = compact_calendar_cli::parse_config(
+        r#"
+        [[ranges]]
+        start = "2024-03-01"
+        end = "2024-03-10"
+        color = "blue"
+
+        [[ranges]]
+        start = "12-20"
+        end = "12-01"
+        color = "red"
+        description = "Holidays"
+        "#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        "Invalid config: Range 2 (Holidays): end 12-01 is before start 12-20"
+    );
+}
+
+#[test]
+fn test_overlapping_ranges_first_wins() {
+    let calendar = build_calendar(2024, "tests/fixtures/overlap.toml", MonthFilter::All);
+    let overlaps = calendar.overlapping_ranges();
+    assert_eq!(overlaps.len(), 1);
+    assert_eq!(overlaps[0].0.description.as_deref(), Some("Sprint"));
+    assert_eq!(overlaps[0].1.description.as_deref(), Some("Trip"));
+
+    let renderer = CalendarRenderer::new(&calendar);
+    assert_eq!(
+        renderer.day_style(date(2024, 3, 14)).get_bg_color(),
+        palette_bg("blue")
+    );
+    assert_eq!(
+        renderer.day_style(date(2024, 3, 18)).get_bg_color(),
+        palette_bg("green")
+    );
+}
+
+#[test]
+fn test_hide_annotations_keeps_grid() {
+    let with = build_calendar(2024, "tests/fixtures/simple.toml", MonthFilter::Single(3));
+    let without = compact_calendar_cli::build_calendar(
+        2024,
+        CalendarOptions {
+            hide_annotations: true,
+            ..common::options(MonthFilter::Single(3))
+        },
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap(),
+    );
+    let with = CalendarRenderer::new(&with).render_to_string();
+    let without_text = CalendarRenderer::new(&without).render_to_string();
+
+    assert!(!without_text.contains("03/15"), "{}", without_text);
+    // The same grid lines, cut off where the annotations began
+    assert_eq!(with.lines().count(), without_text.lines().count());
+    for (full, grid) in with.lines().zip(without_text.lines()) {
+        assert!(full.starts_with(grid), "{}\n{}", full, grid);
+    }
+    assert!(with.contains("│ 11   12   13   14   15   16   17 │03/15"));
+    assert!(without_text.contains("│ 11   12   13   14   15   16   17 │\n"));
+    // Coloring is unaffected
+    let style = CalendarRenderer::new(&without).day_style(date(2024, 3, 15));
+    assert_eq!(style.get_bg_color(), palette_bg("red"));
+}
+
+#[test]
+fn test_weekend_days_from_settings() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+[settings]
+weekend_days = ["fri", "saturday"]
+
+[[ranges]]
+start = "2024-03-04"
+end = "2024-03-17"
+color = "blue"
+"#,
+    )
+    .unwrap();
+    let options = CalendarOptions {
+        weekend_display: WeekendDisplay::Dimmed,
+        color_mode: ColorMode::Work,
+        ..common::options(MonthFilter::Single(3))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let renderer = CalendarRenderer::new(&calendar);
+
+    // 2024-03-08 is a Friday, 03-10 a Sunday
+    assert!(calendar.is_weekend(date(2024, 3, 8)));
+    assert!(!calendar.is_weekend(date(2024, 3, 10)));
+    assert!(!calendar.is_business_day(date(2024, 3, 9)));
+    assert!(calendar.is_business_day(date(2024, 3, 10)));
+
+    // Work mode leaves the configured weekend uncolored, and only it
+    let friday = renderer.day_style(date(2024, 3, 8));
+    assert_eq!(friday.get_bg_color(), None);
+    assert!(friday.get_effects().contains(Effects::DIMMED));
+    let sunday = renderer.day_style(date(2024, 3, 10));
+    assert_eq!(sunday.get_bg_color(), palette_bg("blue"));
+    assert!(!sunday.get_effects().contains(Effects::DIMMED));
+}
+
+#[test]
+fn test_weekend_days_option_overrides_settings() {
+    let config = || {
+        compact_calendar_cli::parse_config("[settings]\nweekend_days = [\"fri\", \"sat\"]").unwrap()
+    };
+    let options = CalendarOptions {
+        weekend_days: Some(vec![Weekday::Sun]),
+        ..common::options(MonthFilter::Single(3))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config());
+    assert_eq!(calendar.weekend_days, vec![Weekday::Sun]);
+
+    let calendar =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::All), config());
+    assert_eq!(calendar.weekend_days, vec![Weekday::Fri, Weekday::Sat]);
+
+    let calendar = compact_calendar_cli::build_calendar(
+        2024,
+        common::options(MonthFilter::All),
+        compact_calendar_cli::parse_config("").unwrap(),
+    );
+    assert_eq!(calendar.weekend_days, DEFAULT_WEEKEND_DAYS);
+}
+
+#[test]
+fn test_invalid_weekend_days() {
+    let error =
+        compact_calendar_cli::parse_config("[settings]\nweekend_days = [\"fri\", \"caturday\"]")
+            .unwrap_err();
+    assert_eq!(
+        error,
+        "Invalid config: Invalid weekday: caturday (expected a name, e.g. sat)"
+    );
+}
+
+#[test]
+fn test_builtin_dimmed_shades_follow_dim_rgb() {
+    for name in BUILTIN_COLORS {
+        let value = ColorPalette::get_color_value(name).unwrap();
+        let derived = dim_rgb(value.normal);
+        let channels = [
+            (value.dimmed.0, derived.0),
+            (value.dimmed.1, derived.1),
+            (value.dimmed.2, derived.2),
+        ];
+        for (tuned, derived) in channels {
+            assert!(
+                tuned.abs_diff(derived) <= 2,
+                "{}: dimmed {:?} is far from {:?}",
+                name,
+                value.dimmed,
+                dim_rgb(value.normal)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_custom_color_dimmed_shade() {
+    let config = compact_calendar_cli::parse_config(
+        r##"
+        [colors]
+        derived = "#649664"
+        chosen = { normal = "#649664", dimmed = [10, 20, 30] }
+        "##,
+    )
+    .unwrap();
+    let calendar =
+        compact_calendar_cli::build_calendar(2024, common::options(MonthFilter::Single(3)), config);
+
+    assert_eq!(
+        calendar.colors["derived"],
+        ColorValue::new(RgbColor(100, 150, 100), RgbColor(70, 105, 70))
+    );
+    assert_eq!(
+        calendar.colors["chosen"],
+        ColorValue::new(RgbColor(100, 150, 100), RgbColor(10, 20, 30))
+    );
+
+    let err = compact_calendar_cli::parse_config(
+        r##"
+        [colors]
+        chosen = { normal = "#649664", dimmed = "#zz0000" }
+        "##,
+    )
+    .unwrap_err();
+    assert!(err.starts_with("Invalid config: Color 'chosen'"), "{}", err);
+}
+
+#[test]
+fn test_dimmed_weekend_range_across_month_boundary() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+[[ranges]]
+start = "2026-01-30"
+end = "2026-02-02"
+color = "blue"
+description = "Offsite"
+"#,
+    )
+    .unwrap();
+    let options = CalendarOptions {
+        weekend_display: WeekendDisplay::Dimmed,
+        today: date(2025, 1, 1),
+        ..common::options(MonthFilter::MonthRange(1, 2))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2026, options, config);
+    let renderer = CalendarRenderer::new(&calendar).with_colors(true);
+    let blue = ColorPalette::get_color_value("blue").unwrap();
+
+    // Saturday 01/31 and Sunday 02/01 sit on either side of the month bar
+    for day in [date(2026, 1, 31), date(2026, 2, 1)] {
+        let style = renderer.day_style(day);
+        assert_eq!(style.get_bg_color(), Some(Color::Rgb(blue.dimmed)));
+    }
+    assert_eq!(
+        renderer.day_style(date(2026, 1, 30)).get_bg_color(),
+        Some(Color::Rgb(blue.normal))
+    );
+
+    let mut output = Vec::new();
+    renderer.render_to_writer(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let week = output
+        .lines()
+        .find(|line| line.starts_with("│W05"))
+        .unwrap();
+    let dimmed = |day: &str| {
+        format!(
+            "\x1b[30m\x1b[48;2;{};{};{}m{}\x1b[0m",
+            blue.dimmed.0, blue.dimmed.1, blue.dimmed.2, day
+        )
+    };
+    assert!(
+        week.contains(&format!("{} │ {} │", dimmed("31"), dimmed("01"))),
+        "{:?}",
+        week
+    );
+
+    let plain = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(
+        plain
+            .contains("│W05 February │ 26   27   28   29   30   31 │ 01 │01/30 to 02/02 - Offsite"),
+        "{}",
+        plain
+    );
+}
+
+#[test]
+fn test_list_colors() {
+    for name in BUILTIN_COLORS {
+        assert!(ColorPalette::get_color_value(name).is_some(), "{}", name);
+    }
+
+    let listing = ColorPalette::new()
+        .with_color_choice(ColorChoice::Always)
+        .list_colors_to_string();
+    let blue = ColorPalette::get_color_value("blue").unwrap();
+    let line = listing.lines().nth(3).unwrap();
+    assert_eq!(
+        line,
+        format!(
+            "\x1b[48;2;{};{};{}m   \x1b[0m\x1b[48;2;{};{};{}m   \x1b[0m blue",
+            blue.normal.0,
+            blue.normal.1,
+            blue.normal.2,
+            blue.dimmed.0,
+            blue.dimmed.1,
+            blue.dimmed.2
+        )
+    );
+    assert_eq!(listing.lines().count(), BUILTIN_COLORS.len());
+
+    let names = ColorPalette::new()
+        .with_color_choice(ColorChoice::Never)
+        .list_colors_to_string();
+    assert_eq!(names, BUILTIN_COLORS.join("\n") + "\n");
+}
+
+#[test]
+fn test_event_effects() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+[dates.2026-01-14]
+description = "Retro"
+effects = ["underline"]
+
+[dates.2026-01-20]
+description = "Launch"
+color = "red"
+effects = ["Bold", "italic"]
+
+[[ranges]]
+start = "2026-01-15"
+end = "2026-01-16"
+color = "blue"
+description = "Offsite"
+effects = ["strikethrough"]
+"#,
+    )
+    .unwrap();
+    let options = CalendarOptions {
+        past_date_display: PastDateDisplay::Strikethrough,
+        ..common::options(MonthFilter::Single(1))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2026, options, config);
+    let renderer = CalendarRenderer::new(&calendar).with_colors(true);
+
+    // Combined with the automatic past and today effects
+    let effects = |day| renderer.day_style(date(2026, 1, day)).get_effects();
+    assert_eq!(effects(14), Effects::STRIKETHROUGH | Effects::UNDERLINE);
+    assert_eq!(effects(15), Effects::UNDERLINE | Effects::STRIKETHROUGH);
+    assert_eq!(effects(16), Effects::STRIKETHROUGH);
+    assert_eq!(effects(20), Effects::BOLD | Effects::ITALIC);
+    assert_eq!(effects(21), Effects::new());
+
+    let mut output = Vec::new();
+    renderer.render_to_writer(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let red = ColorPalette::get_color_value("red").unwrap().normal;
+    let launch = format!(
+        "\x1b[1m\x1b[3m\x1b[30m\x1b[48;2;{};{};{}m01/20 - Launch\x1b[0m",
+        red.0, red.1, red.2
+    );
+    assert!(output.contains(&launch), "{:?}", output);
+    assert!(
+        output.contains("\x1b[4m01/14 - Retro\x1b[0m"),
+        "{:?}",
+        output
+    );
+}
+
+#[test]
+fn test_invalid_event_effect() {
+    let err = compact_calendar_cli::parse_config(
+        r#"
+[[ranges]]
+start = "03-01"
+end = "03-02"
+color = "blue"
+description = "Trip"
+effects = ["blink"]
+"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        "Invalid config: Range 1 (Trip): Invalid effect: 'blink'. \
+         Use bold, italic, underline or strikethrough"
+    );
+}
+
+#[test]
+fn test_range_effect_sets_ranges_apart() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+[dates.2024-03-05]
+description = "Demo"
+color = "blue"
+
+[dates.2024-03-12]
+description = "Checkpoint"
+color = "red"
+
+[[ranges]]
+start = "2024-03-11"
+end = "2024-03-13"
+color = "blue"
+description = "Sprint"
+"#,
+    )
+    .unwrap();
+    let build = |range_effect| {
+        let options = CalendarOptions {
+            range_effect,
+            ..common::options(MonthFilter::Single(3))
+        };
+        compact_calendar_cli::build_calendar(2024, options, config.clone())
+    };
+
+    let calendar = build(Some(Effects::UNDERLINE));
+    let renderer = CalendarRenderer::new(&calendar);
+    let effects = |day| renderer.day_style(date(2024, 3, day)).get_effects();
+    // Same color, but only the range's days are underlined
+    assert_eq!(effects(5), Effects::new());
+    assert_eq!(effects(11), Effects::UNDERLINE);
+    assert_eq!(effects(13), Effects::UNDERLINE);
+    // The event's own color wins, and so does its style
+    assert_eq!(effects(12), Effects::new());
+
+    let calendar = build(None);
+    let style = CalendarRenderer::new(&calendar).day_style(date(2024, 3, 11));
+    assert_eq!(style.get_effects(), Effects::new());
+}
+
+#[test]
+fn test_ranges_crossing_the_year_boundary() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+[[ranges]]
+start = "2023-12-28"
+end = "2024-01-03"
+color = "red"
+description = "Old break"
+
+[[ranges]]
+start = "2024-12-20"
+end = "2025-01-05"
+color = "blue"
+description = "Break"
+"#,
+    )
+    .unwrap();
+    let calendar = compact_calendar_cli::build_calendar(
+        2024,
+        common::options(MonthFilter::All),
+        config.clone(),
+    );
+    let renderer = CalendarRenderer::new(&calendar);
+    let output = renderer.render_to_string();
+
+    // The year is added to whichever end falls outside 2024
+    assert!(
+        output.contains(
+            "│W01 January  │ 01   02   03   04   05   06   07 │12/28/23 to 01/03 - Old break"
+        ),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("│ 16   17   18   19   20   21   22 │12/20 to 01/05/25 - Break"),
+        "{}",
+        output
+    );
+    assert_eq!(output.matches("Break").count(), 1);
+
+    // Only the visible days are colored
+    assert_eq!(
+        renderer.day_style(date(2024, 1, 3)).get_bg_color(),
+        palette_bg("red")
+    );
+    assert_eq!(renderer.day_style(date(2024, 1, 4)).get_bg_color(), None);
+    assert_eq!(
+        renderer.day_style(date(2024, 12, 31)).get_bg_color(),
+        palette_bg("blue")
+    );
+    assert!(
+        output.contains("│ 30   31 │ 01   02   03   04   05 │\n"),
+        "{}",
+        output
+    );
+
+    let options = CalendarOptions {
+        annotation_style: AnnotationStyle::Long,
+        ..common::options(MonthFilter::Single(12))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let output = CalendarRenderer::new(&calendar).render_to_string();
+    assert!(
+        output.contains("December 20th to January 5th, 2025 - Break"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_dim_weekends_per_range_and_event() {
+    let config = compact_calendar_cli::parse_config(
+        r#"
+[[ranges]]
+start = "2025-03-10"
+end = "2025-03-23"
+color = "blue"
+description = "Vacation"
+dim_weekends = false
+
+[dates."2025-03-29"]
+description = "Hike"
+color = "green"
+"#,
+    )
+    .unwrap();
+    let options = CalendarOptions {
+        weekend_display: WeekendDisplay::Dimmed,
+        ..common::options(MonthFilter::Single(3))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2025, options, config);
+    let renderer = CalendarRenderer::new(&calendar).with_colors(true);
+    let blue = ColorPalette::get_color_value("blue").unwrap();
+    let green = ColorPalette::get_color_value("green").unwrap();
+
+    // The vacation stays solid through its weekends, the hike still dims
+    assert_eq!(
+        renderer.day_style(date(2025, 3, 15)).get_bg_color(),
+        Some(Color::Rgb(blue.normal))
+    );
+    assert_eq!(
+        renderer.day_style(date(2025, 3, 29)).get_bg_color(),
+        Some(Color::Rgb(green.dimmed))
+    );
+
+    // And an event can ask to dim when weekends otherwise aren't
+    let config = compact_calendar_cli::parse_config(
+        r#"
+[dates."2025-03-29"]
+description = "Hike"
+color = "green"
+dim_weekends = true
+"#,
+    )
+    .unwrap();
+    let calendar =
+        compact_calendar_cli::build_calendar(2025, common::options(MonthFilter::Single(3)), config);
+    let renderer = CalendarRenderer::new(&calendar).with_colors(true);
+    assert_eq!(
+        renderer.day_style(date(2025, 3, 29)).get_bg_color(),
+        Some(Color::Rgb(green.dimmed))
+    );
+    assert!(!calendar.dims_color(date(2025, 3, 28)));
+}
+
+#[test]
+fn test_render_colored_to_string_keeps_escapes() {
+    let calendar = compact_calendar_cli::build_calendar(
+        2026,
+        CalendarOptions {
+            weekend_display: WeekendDisplay::Dimmed,
+            ..common::options(MonthFilter::Single(1))
+        },
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap(),
+    );
+    // Colors are kept even when the renderer itself has them off
+    let renderer = CalendarRenderer::new(&calendar).with_colors(false);
+    let colored = renderer.render_colored_to_string();
+
+    assert!(colored.contains("\x1b[2m10\x1b[0m"), "{:?}", colored);
+    assert!(colored.contains("\x1b[4m\x1b[30m\x1b[48;2;89;194;255m15"));
+    assert!(colored.contains("\x1b[30m\x1b[48;2;89;194;255m05\x1b[0m"));
+    assert!(colored.contains("\x1b[30m\x1b[48;2;89;194;255m01/01 to 01/07 - New Year Week"));
+    assert!(!renderer.render_to_string().contains('\x1b'));
+}
+
+#[test]
+fn test_rendering_uses_options_today_not_the_clock() {
+    let options = CalendarOptions {
+        today: date(2030, 6, 12),
+        past_date_display: PastDateDisplay::Strikethrough,
+        ..common::options(MonthFilter::Single(6))
+    };
+    let calendar = compact_calendar_cli::build_calendar(
+        2030,
+        options,
+        compact_calendar_cli::parse_config("").unwrap(),
+    );
+    let output = CalendarRenderer::new(&calendar).render_colored_to_string();
+
+    // Every day is styled against the one date in the options
+    assert_eq!(output.matches("\x1b[4m").count(), 1, "{}", output);
+    assert!(output.contains("\x1b[4m12\x1b[0m"), "{}", output);
+    assert!(output.contains("\x1b[9m11\x1b[0m"), "{}", output);
+    assert!(output.contains("   13   14 "), "{}", output);
+}
+
+#[test]
+fn test_ascii_border_style_keeps_every_glyph_ascii() {
+    let config =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/milestones.toml"))
+            .unwrap();
+    let options = CalendarOptions {
+        border_style: BorderStyle::Ascii,
+        framed: true,
+        legend: true,
+        week_heat: true,
+        year_progress: true,
+        footnotes: true,
+        ..common::options(MonthFilter::MonthRange(4, 5))
+    };
+    let calendar = compact_calendar_cli::build_calendar(2024, options, config);
+    let renderer = CalendarRenderer::new(&calendar);
+
+    let output = renderer.render_to_string();
+    assert!(output.is_ascii(), "{}", output);
+    assert!(output.contains(">10"), "{}", output);
+    assert!(output.contains("  ## purple"), "{}", output);
+    assert!(
+        output.contains("[####################] 100% of 2024"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("| 1 event - 0 ranges - 45 workdays "),
+        "{}",
+        output
+    );
+
+    let freebusy = renderer.render_freebusy_to_string();
+    assert!(freebusy.is_ascii(), "{}", freebusy);
+    assert!(freebusy.contains("#"), "{}", freebusy);
+}
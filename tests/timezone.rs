@@ -0,0 +1,26 @@
+#![cfg(feature = "timezone")]
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use compact_calendar_cli::timezone::{parse_timezone, today_in};
+
+#[test]
+fn test_today_differs_across_date_line() {
+    let instant = Utc.with_ymd_and_hms(2024, 6, 30, 23, 30, 0).unwrap();
+
+    let kiritimati = parse_timezone("Pacific/Kiritimati").unwrap();
+    let pago_pago = parse_timezone("Pacific/Pago_Pago").unwrap();
+
+    assert_eq!(
+        today_in(kiritimati, instant),
+        NaiveDate::from_ymd_opt(2024, 7, 1).unwrap()
+    );
+    assert_eq!(
+        today_in(pago_pago, instant),
+        NaiveDate::from_ymd_opt(2024, 6, 30).unwrap()
+    );
+}
+
+#[test]
+fn test_invalid_timezone() {
+    assert!(parse_timezone("Mars/Olympus_Mons").is_err());
+}
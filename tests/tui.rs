@@ -0,0 +1,36 @@
+#![cfg(feature = "tui")]
+
+use chrono::NaiveDate;
+use compact_calendar_cli::tui::{NavKey, NavState};
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 1, 15).unwrap()
+}
+
+#[test]
+fn test_next_month_from_december_advances_year() {
+    let mut state = NavState::new(2024, 12);
+    state.apply(NavKey::NextMonth, today());
+    assert_eq!(state, NavState::new(2025, 1));
+}
+
+#[test]
+fn test_prev_month_from_january_goes_back_a_year() {
+    let mut state = NavState::new(2024, 1);
+    state.apply(NavKey::PrevMonth, today());
+    assert_eq!(state, NavState::new(2023, 12));
+}
+
+#[test]
+fn test_year_keys_and_jump_to_today() {
+    let mut state = NavState::new(2024, 6);
+    state.apply(NavKey::NextYear, today());
+    assert_eq!(state, NavState::new(2025, 6));
+    state.apply(NavKey::PrevYear, today());
+    state.apply(NavKey::PrevYear, today());
+    assert_eq!(state, NavState::new(2023, 6));
+    state.apply(NavKey::Today, today());
+    assert_eq!(state, NavState::new(2026, 1));
+    state.apply(NavKey::Quit, today());
+    assert!(state.quit);
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "yaml")]
+
+use compact_calendar_cli::config::ConfigFormat;
+use std::path::PathBuf;
+
+#[test]
+fn test_yaml_config_matches_toml() {
+    let yaml =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.yaml")).unwrap();
+    let toml =
+        compact_calendar_cli::load_config(&PathBuf::from("tests/fixtures/simple.toml")).unwrap();
+
+    for date in ["01-15", "02-14", "02-01"] {
+        assert_eq!(
+            yaml.dates[date][0].description,
+            toml.dates[date][0].description
+        );
+        assert_eq!(yaml.dates[date][0].color, toml.dates[date][0].color);
+    }
+    assert_eq!(yaml.ranges.len(), 2);
+    for (yaml, toml) in yaml.ranges.iter().zip(&toml.ranges) {
+        assert_eq!(
+            (&yaml.start, &yaml.end, &yaml.color, &yaml.description),
+            (&toml.start, &toml.end, &toml.color, &toml.description)
+        );
+    }
+}
+
+#[test]
+fn test_yaml_errors_name_the_format() {
+    let err = compact_calendar_cli::parse_config_as("dates: [", ConfigFormat::Yaml).unwrap_err();
+    assert!(err.starts_with("Failed to parse YAML config: "), "{}", err);
+
+    let err = compact_calendar_cli::parse_config_as(
+        "dates:\n  \"03-05\":\n    description: Dentist\n    severity: 9\n",
+        ConfigFormat::Yaml,
+    )
+    .unwrap_err();
+    assert!(err.starts_with("Invalid config: "), "{}", err);
+}